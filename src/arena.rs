@@ -0,0 +1,238 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::convert::Infallible;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, Slot};
+use crate::container::{buf_emplace, dangling_slot, Buffered, CapacityHint, Emplace};
+use crate::container_core::construct_in;
+
+type Chunk = NonNull<[MaybeUninit<u8>]>;
+
+/// A growable container for multiple objects.
+///
+/// Unlike other containers that are borrowed exclusively by the constructed
+/// object, an arena is shared by all objects placed in it. Therefore, it can
+/// hand out multiple live pointers at once. Objects are placed in chunks of
+/// memory, which are allocated as needed and released only when the arena is
+/// dropped or [`reset`](Self::reset).
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, Arena, Buffered, Dynify, Fn};
+/// # use std::future::Future;
+/// # pollster::block_on(async {
+/// let arena = Arena::new();
+/// let futs = (0..4)
+///     .map(|i| {
+///         let constructor: Fn!(i32 => dyn Future<Output = i32>) =
+///             from_fn!(|i| async move { i * 2 }, i);
+///         constructor.init(&arena)
+///     })
+///     .collect::<Vec<Buffered<_>>>();
+/// for (i, fut) in futs.into_iter().enumerate() {
+///     assert_eq!(fut.await, i as i32 * 2);
+/// }
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Arena {
+    chunks: UnsafeCell<Vec<Chunk>>,
+    cursor: Cell<usize>,
+}
+impl Arena {
+    /// The minimal size of a newly allocated chunk.
+    const MIN_CHUNK_SIZE: usize = 64;
+
+    /// Creates a new arena without allocation.
+    pub const fn new() -> Self {
+        Self {
+            chunks: UnsafeCell::new(Vec::new()),
+            cursor: Cell::new(0),
+        }
+    }
+
+    /// Creates a new arena with at least the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let arena = Self::new();
+        if capacity != 0 {
+            unsafe { arena.alloc_chunk(capacity) };
+        }
+        arena
+    }
+
+    /// Returns the total number of bytes allocated by this arena.
+    pub fn allocated_bytes(&self) -> usize {
+        unsafe { (*self.chunks.get()).iter().map(|c| c.len()).sum() }
+    }
+
+    /// Releases all memory except the last chunk, which is kept for reuse.
+    ///
+    /// An exclusive reference ensures no objects live in this arena. Note that
+    /// the destructors of leaked objects, if any, will never run.
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        let last = chunks.pop();
+        chunks.drain(..).for_each(|c| unsafe { free_chunk(c) });
+        chunks.extend(last);
+        self.cursor.set(0);
+    }
+
+    /// Reserves a memory block of the specified layout.
+    unsafe fn alloc_slot(&self, layout: Layout) -> Slot<'_> {
+        if layout.size() == 0 {
+            return dangling_slot(layout);
+        }
+        if let Some(slot) = self.alloc_in_last(layout) {
+            return slot;
+        }
+        // The worst case is that the chunk is not aligned at all.
+        let required = layout.size() + layout.align() - 1;
+        let last_len = (*self.chunks.get()).last().map_or(0, |c| c.len());
+        self.alloc_chunk(Ord::max(required, last_len * 2));
+        self.alloc_in_last(layout)
+            .unwrap_or_else(|| unreachable!("new chunk must fit the layout"))
+    }
+
+    /// Reserves a memory block in the last chunk, returning `None` if it lacks
+    /// sufficient free space.
+    unsafe fn alloc_in_last(&self, layout: Layout) -> Option<Slot<'_>> {
+        let last = *(*self.chunks.get()).last()?;
+        let cursor = self.cursor.get();
+        // Only the free part is borrowed, as other parts may be in use.
+        let free = core::slice::from_raw_parts_mut(
+            last.cast::<MaybeUninit<u8>>().as_ptr().add(cursor),
+            last.len() - cursor,
+        );
+        let slot = buf_emplace(free, layout).ok()?;
+        let start = last.cast::<u8>().as_ptr();
+        let offset = slot.as_ptr().as_ptr().offset_from(start) as usize;
+        self.cursor.set(offset + layout.size());
+        Some(slot)
+    }
+
+    /// Allocates a new chunk of at least the specified size.
+    unsafe fn alloc_chunk(&self, size: usize) {
+        let size = Ord::max(size, Self::MIN_CHUNK_SIZE);
+        let chunk = alloc::vec![MaybeUninit::uninit(); size].into_boxed_slice();
+        let chunk = NonNull::new_unchecked(Box::into_raw(chunk));
+        (*self.chunks.get()).push(chunk);
+        self.cursor.set(0);
+    }
+}
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Drop for Arena {
+    fn drop(&mut self) {
+        self.chunks
+            .get_mut()
+            .drain(..)
+            .for_each(|c| unsafe { free_chunk(c) });
+    }
+}
+impl fmt::Debug for Arena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Arena")
+            .field("allocated_bytes", &self.allocated_bytes())
+            .finish()
+    }
+}
+
+// SAFETY: `Arena` only holds plain bytes, and any object living in it borrows
+// the arena.
+unsafe impl Send for Arena {}
+
+unsafe fn free_chunk(chunk: Chunk) {
+    drop(Box::from_raw(chunk.as_ptr()));
+}
+
+unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a Arena {
+    type Ptr = Buffered<'a, T>;
+    type Err = Infallible;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let layout = constructor.layout();
+            // The memory block is reserved before construction, so it's fine
+            // if `construct()` places other objects in this arena.
+            let slot = self.alloc_slot(layout);
+            let init = construct_in(slot, constructor, layout);
+            Ok(Buffered::from_raw(init))
+        }
+    }
+}
+impl CapacityHint for &Arena {
+    fn can_fit(&self, _: Layout) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+
+    use super::*;
+    use crate::utils::{randarr, randstr, DropCounter, OpqAny};
+    use crate::{from_closure, Dynify};
+
+    #[test]
+    fn hold_multiple_objects() {
+        let arena = Arena::new();
+        let inps = (0..64).map(|_| randstr(8..64)).collect::<Vec<_>>();
+        let outs = inps
+            .iter()
+            .map(|inp| from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny).init(&arena))
+            .collect::<Vec<Buffered<dyn Any>>>();
+        for (inp, out) in inps.iter().zip(outs) {
+            assert_eq!(out.downcast_ref::<String>(), Some(inp));
+        }
+    }
+
+    #[test]
+    fn init_large_object() {
+        let arena = Arena::with_capacity(8);
+        let inp = randarr::<256>();
+        let out = from_closure(|slot| slot.write(inp) as &mut OpqAny).init(&arena);
+        assert_eq!(out.downcast_ref::<[u8; 256]>(), Some(&inp));
+        assert!(arena.allocated_bytes() >= 256 + 8);
+    }
+
+    #[test]
+    fn init_inside_construct() {
+        let arena = Arena::new();
+        let inp = randstr(8..64);
+        let init = from_closure(|slot| {
+            let inner = from_closure(|slot| slot.write(inp.clone())).init(&arena);
+            slot.write(inner)
+        });
+        let out = init.init(&arena);
+        assert_eq!(**out, inp);
+    }
+
+    #[test]
+    fn reset_arena() {
+        let mut arena = Arena::new();
+        for _ in 0..4 {
+            let init = from_closure(|slot| slot.write(randarr::<128>()) as &mut OpqAny);
+            drop(init.init(&arena));
+        }
+        arena.reset();
+        assert!(arena.allocated_bytes() > 0);
+
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        drop(init.init(&arena));
+        assert_eq!(DropCounter::count(), 1);
+    }
+}
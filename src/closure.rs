@@ -1,8 +1,11 @@
 use core::alloc::Layout;
+use core::fmt;
 use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 use core::ptr::NonNull;
 
-use crate::constructor::{Construct, Opaque, PinConstruct, Slot};
+use crate::constructor::{Construct, DebugConstruct, Opaque, PinConstruct, Slot};
+use crate::container::validate_slot;
 
 /// The constructor created by [`from_closure`].
 #[must_use = "constructor must be initialized"]
@@ -27,7 +30,7 @@ where
     }
     unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
         let ptr = (self.0)(slot.cast());
-        NonNull::from(ptr.as_mut())
+        NonNull::from(ptr.get_mut())
     }
 }
 unsafe impl<T, U, F> Construct for Closure<T, F>
@@ -36,6 +39,27 @@ where
     F: FnOnce(Slot<T>) -> &mut Opaque<U>,
 {
 }
+impl<T, F> DebugConstruct for Closure<T, F>
+where
+    Self: PinConstruct,
+{
+    fn object_type_name(&self) -> &'static str {
+        core::any::type_name::<T>()
+    }
+}
+impl<T, F> fmt::Debug for Closure<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Closure")
+            .field("object", &core::any::type_name::<T>())
+            .field("layout", &Layout::new::<T>())
+            .finish_non_exhaustive()
+    }
+}
+impl<T, F: Clone> Clone for Closure<T, F> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
 
 /// Creates a new closure constructor.
 ///
@@ -73,11 +97,211 @@ where
     Closure(f, PhantomData)
 }
 
+/// The constructor created by [`from_pin_closure`].
+#[must_use = "constructor must be initialized"]
+pub struct PinClosure<T, F>(F, PhantomData<T>);
+// SAFETY: See the safety notes of `Closure`.
+unsafe impl<T, U, F> PinConstruct for PinClosure<T, F>
+where
+    U: ?Sized,
+    F: FnOnce(Slot<T>) -> &mut Opaque<U>,
+{
+    type Object = U;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = (self.0)(slot.cast());
+        NonNull::from(ptr.get_mut())
+    }
+}
+
+impl<T, F> DebugConstruct for PinClosure<T, F>
+where
+    Self: PinConstruct,
+{
+    fn object_type_name(&self) -> &'static str {
+        core::any::type_name::<T>()
+    }
+}
+impl<T, F> fmt::Debug for PinClosure<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PinClosure")
+            .field("object", &core::any::type_name::<T>())
+            .field("layout", &Layout::new::<T>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates a new closure constructor that requires pinned memory blocks.
+///
+/// It works the same as [`from_closure`], except that the returned constructor
+/// implements [`PinConstruct`] only. In other words, `f` is guaranteed to be
+/// invoked with a slot that will not be moved until the written object is
+/// dropped, so that the object may safely refer to its own address.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_pin_closure, PinDynify, Slot};
+/// # use std::marker::PhantomPinned;
+/// # use std::ptr::NonNull;
+/// struct Node {
+///     this: NonNull<u8>,
+///     _pinned: PhantomPinned,
+/// }
+///
+/// let init = from_pin_closure(|slot: Slot<Node>| {
+///     let this = slot.into_raw();
+///     // SAFETY: The slot is recreated from the same memory block.
+///     let slot = unsafe { Slot::new_unchecked(this).cast() };
+///     slot.write(Node {
+///         this,
+///         _pinned: PhantomPinned,
+///     })
+/// });
+/// let node = init.pin_boxed();
+/// assert_eq!(
+///     node.this.as_ptr().cast_const(),
+///     &*node as *const Node as *const u8
+/// );
+/// ```
+///
+/// Such constructors cannot be used with containers that may move the object:
+///
+/// ```rust,compile_fail
+/// # use dynify::{from_pin_closure, Dynify};
+/// let init = from_pin_closure(|slot| slot.write(123));
+/// let _ = init.boxed();
+/// ```
+#[inline(always)]
+pub fn from_pin_closure<T, U, F>(f: F) -> PinClosure<T, F>
+where
+    U: ?Sized,
+    F: FnOnce(Slot<T>) -> &mut Opaque<U>,
+{
+    PinClosure(f, PhantomData)
+}
+
+/// The constructor created by [`from_raw_parts`].
+#[must_use = "constructor must be initialized"]
+pub struct RawParts<T: ?Sized, F>(Layout, F, PhantomData<fn() -> *const T>);
+// SAFETY: The contracts are upheld by the caller of `from_raw_parts`.
+unsafe impl<T, F> PinConstruct for RawParts<T, F>
+where
+    T: ?Sized,
+    F: FnOnce(Slot) -> NonNull<T>,
+{
+    type Object = T;
+    fn layout(&self) -> Layout {
+        self.0
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr();
+        let init = (self.1)(slot);
+        validate_slot(ptr, self.0, init);
+        init
+    }
+}
+unsafe impl<T, F> Construct for RawParts<T, F>
+where
+    T: ?Sized,
+    F: FnOnce(Slot) -> NonNull<T>,
+{
+}
+
+/// Creates a new constructor from a layout and an initialization function.
+///
+/// When the returned instance gets [`construct`]ed, `init` is invoked with a
+/// slot satisfying `layout`, and its return value is then used as the object
+/// pointer. This is useful to construct objects produced by foreign code, such
+/// as C libraries or plugins, in containers. In debug builds, the returned
+/// pointer is checked against the slot and `layout`.
+///
+/// # Safety
+///
+/// - `init` must write an object of `layout` to the supplied slot and return a
+///   pointer to it, which must have the same address as the slot.
+/// - The metadata of the returned pointer, if any, must describe an object of
+///   `layout`.
+/// - The object may not rely on its address, i.e., it can be moved freely.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_raw_parts, Dynify};
+/// # use std::alloc::Layout;
+/// // Suppose this function is provided by a C library.
+/// unsafe extern "C" fn point_init(ptr: *mut u8, x: i32, y: i32) {
+///     ptr.cast::<[i32; 2]>().write([x, y]);
+/// }
+///
+/// let init = unsafe {
+///     from_raw_parts(Layout::new::<[i32; 2]>(), |slot| {
+///         let ptr = slot.into_raw();
+///         point_init(ptr.as_ptr(), 1, 2);
+///         ptr.cast::<[i32; 2]>()
+///     })
+/// };
+/// assert_eq!(*init.boxed(), [1, 2]);
+/// ```
+///
+/// [`construct`]: PinConstruct::construct
+#[inline(always)]
+pub unsafe fn from_raw_parts<T, F>(layout: Layout, init: F) -> RawParts<T, F>
+where
+    T: ?Sized,
+    F: FnOnce(Slot) -> NonNull<T>,
+{
+    RawParts(layout, init, PhantomData)
+}
+
+/// The constructor created by [`Dynify::map_object`].
+///
+/// [`Dynify::map_object`]: crate::Dynify::map_object
+#[must_use = "constructor must be initialized"]
+pub struct MapObject<C, T, F>(C, F, PhantomData<T>);
+// SAFETY: The inner object is constructed in a temporary memory block, which
+// is then moved to `f`. This is sound since `C` does not require a pinned
+// memory block. The rest is the same as `Closure`.
+unsafe impl<C, T, U, F> PinConstruct for MapObject<C, T, F>
+where
+    C: Construct,
+    C::Object: Sized,
+    U: ?Sized,
+    F: FnOnce(Slot<T>, C::Object) -> &mut Opaque<U>,
+{
+    type Object = U;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let mut object = MaybeUninit::<C::Object>::uninit();
+        let ptr = NonNull::from(&mut object).cast::<u8>();
+        let init = self.0.construct(Slot::new_unchecked(ptr));
+        validate_slot(ptr, Layout::new::<C::Object>(), init);
+        let ptr = (self.1)(slot.cast(), object.assume_init());
+        NonNull::from(ptr.get_mut())
+    }
+}
+unsafe impl<C, T, U, F> Construct for MapObject<C, T, F>
+where
+    C: Construct,
+    C::Object: Sized,
+    U: ?Sized,
+    F: FnOnce(Slot<T>, C::Object) -> &mut Opaque<U>,
+{
+}
+
+pub(crate) fn map_object<C, T, F>(constructor: C, f: F) -> MapObject<C, T, F> {
+    MapObject(constructor, f, PhantomData)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::{randstr, OpqStrFut};
-    use crate::PinDynify;
+    use crate::utils::{randstr, DropCounter, OpqAny, OpqStrFut};
+    use crate::{Dynify, PinDynify};
 
     #[pollster::test]
     async fn from_closure_works() {
@@ -85,4 +309,90 @@ mod tests {
         let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
         assert_eq!(init.pin_boxed().await, inp);
     }
+
+    #[test]
+    fn debug_closure() {
+        let init = from_closure(|slot| slot.write(String::new()) as &mut OpqAny);
+        assert!(init.object_type_name().contains("String"));
+        let msg = format!("{init:?}");
+        assert!(msg.starts_with("Closure { object: ") && msg.contains("String"));
+
+        let init = from_pin_closure(|slot| slot.write(0u8));
+        assert!(init.object_type_name().contains("u8"));
+    }
+
+    #[test]
+    fn from_pin_closure_works() {
+        struct Node(NonNull<u8>, String, std::marker::PhantomPinned);
+        let new_init = |inp: String| {
+            from_pin_closure(move |slot: Slot<Node>| {
+                let this = slot.into_raw();
+                let slot = unsafe { Slot::new_unchecked(this).cast() };
+                slot.write(Node(this, inp, std::marker::PhantomPinned))
+            })
+        };
+        let is_pinned = |node: &Node| std::ptr::eq(node.0.as_ptr(), node as *const _ as *const u8);
+
+        let inp = randstr(8..64);
+        let mut stk = crate::pin_buffer!(64);
+        let out = new_init(inp.clone()).pin_init(stk.as_mut());
+        assert!(is_pinned(&out));
+        assert_eq!(out.1, inp);
+        drop(out);
+
+        let out = new_init(inp.clone()).pin_boxed();
+        assert!(is_pinned(&out));
+        assert_eq!(out.1, inp);
+    }
+
+    #[test]
+    fn from_raw_parts_works() {
+        let inp = randstr(8..64);
+        let init = unsafe {
+            from_raw_parts(Layout::new::<String>(), |slot| {
+                let ptr = slot.into_raw().cast::<String>();
+                ptr.as_ptr().write(inp.clone());
+                ptr as NonNull<dyn std::any::Any>
+            })
+        };
+        assert_eq!(init.boxed().downcast_ref::<String>(), Some(&inp));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "initialized layout mismatches"]
+    fn panic_on_mismatched_raw_parts() {
+        let init = unsafe {
+            from_raw_parts(Layout::new::<u64>(), |slot| {
+                let ptr = slot.into_raw().cast::<u32>();
+                ptr.as_ptr().write(0);
+                ptr
+            })
+        };
+        drop(init.boxed());
+    }
+
+    #[pollster::test]
+    async fn map_object_works() {
+        struct Wrapper<F>(F, DropCounter);
+        impl<F: std::future::Future> std::future::Future for Wrapper<F> {
+            type Output = F::Output;
+            fn poll(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Self::Output> {
+                unsafe { self.map_unchecked_mut(|this| &mut this.0) }.poll(cx)
+            }
+        }
+
+        let inp = randstr(8..64);
+        let init = from_closure(|slot| slot.write(async { inp.clone() }))
+            .map_object(|slot, fut| slot.write(Wrapper(fut, DropCounter)) as &mut OpqStrFut);
+        assert_eq!(init.pin_boxed().await, inp);
+        assert_eq!(DropCounter::count(), 1);
+
+        let init = from_closure(|slot| slot.write(inp.len()))
+            .map_object(|slot, len| slot.write(len * 2) as &mut OpqAny);
+        assert_eq!(init.boxed().downcast_ref::<usize>(), Some(&(inp.len() * 2)));
+    }
 }
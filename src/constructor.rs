@@ -1,6 +1,7 @@
 use core::alloc::Layout;
 use core::fmt;
 use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 use core::pin::Pin;
 use core::ptr::NonNull;
 
@@ -95,6 +96,70 @@ pub unsafe trait PinConstruct: Sized {
 /// [`construct`]: PinConstruct::construct
 pub unsafe trait Construct: PinConstruct {}
 
+/// A constructor that can be used multiple times.
+///
+/// It is implemented for all constructors that implement [`Clone`], such as
+/// constructors created by [`from_fn!`] with cloneable arguments, so that the
+/// same object can be constructed in several containers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, CloneConstruct, Dynify, Fn};
+/// # use std::future::Future;
+/// # use std::mem::MaybeUninit;
+/// async fn work(id: usize) -> usize {
+///     id * 2
+/// }
+///
+/// fn run_all(init: impl CloneConstruct<Object = dyn Future<Output = usize>>) -> Vec<usize> {
+///     let mut stacks = [[MaybeUninit::<u8>::uninit(); 32]; 4];
+///     stacks
+///         .iter_mut()
+///         .map(|stack| pollster::block_on(init.clone().init(stack)))
+///         .collect()
+/// }
+///
+/// let id = 21;
+/// let init: Fn!(usize => dyn Future<Output = usize>) = from_fn!(work, id);
+/// assert_eq!(run_all(init), [42; 4]);
+/// ```
+///
+/// [`from_fn!`]: crate::from_fn
+pub trait CloneConstruct: Construct + Clone {}
+impl<T: Construct + Clone> CloneConstruct for T {}
+
+/// A constructor that provides human-readable information for debugging.
+///
+/// Along with [`PinConstruct::layout`], it helps to figure out which
+/// constructor fails to fit in a container. It is implemented by constructors
+/// created by [`from_fn!`] and [`from_closure`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, DebugConstruct, Dynify, PinConstruct};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 8];
+/// let init = from_closure(|slot| slot.write([0u64; 4]));
+/// let (init, _) = init.try_init(&mut stack).unwrap_err();
+/// assert!(init.object_type_name().contains("u64"));
+/// assert_eq!(init.layout().size(), 32);
+/// ```
+///
+/// [`from_fn!`]: crate::from_fn
+/// [`from_closure`]: crate::from_closure
+pub trait DebugConstruct: PinConstruct {
+    /// Returns the name of the type of the object to be constructed.
+    ///
+    /// For unsized objects, this returns the name of the original type of the
+    /// coerced DST. Like [`type_name`], the returned name is only meant for
+    /// diagnostics and its format is not guaranteed.
+    ///
+    /// [`type_name`]: core::any::type_name
+    fn object_type_name(&self) -> &'static str;
+}
+
 unsafe impl<T: PinConstruct> PinConstruct for &'_ mut Option<T> {
     type Object = T::Object;
     fn layout(&self) -> Layout {
@@ -110,6 +175,206 @@ unsafe impl<T: PinConstruct> PinConstruct for &'_ mut Option<T> {
 }
 unsafe impl<T: Construct> Construct for &'_ mut Option<T> {}
 
+/// The constructor created by [`from_option`].
+#[must_use = "constructor must be initialized"]
+pub struct Taken<'a, T>(&'a mut Option<T>);
+unsafe impl<T: PinConstruct> PinConstruct for Taken<'_, T> {
+    type Object = T::Object;
+    fn layout(&self) -> Layout {
+        unwrap_unchecked(self.0.as_ref()).layout()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        unwrap_unchecked(self.0.take()).construct(slot)
+    }
+}
+unsafe impl<T: Construct> Construct for Taken<'_, T> {}
+
+/// Borrows the constructor in `option`, returning `None` if it is empty.
+///
+/// Unlike `&mut Option<T>`, which panics if the constructor has been consumed,
+/// the returned constructor is guaranteed to hold one. The constructor is taken
+/// out of `option` only if it gets constructed, so that it can be retried in
+/// other containers after failures.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_closure, from_option, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut small = [MaybeUninit::<u8>::uninit(); 4];
+/// let mut large = [MaybeUninit::<u8>::uninit(); 32];
+///
+/// let mut init = Some(from_closure(|slot| slot.write([0u32; 4])));
+/// assert!(from_option(&mut init)
+///     .unwrap()
+///     .try_init(&mut small)
+///     .is_err());
+/// let val = from_option(&mut init).unwrap().init(&mut large);
+/// assert_eq!(*val, [0; 4]);
+/// assert!(from_option(&mut init).is_none());
+/// ```
+pub fn from_option<T: PinConstruct>(option: &mut Option<T>) -> Option<Taken<'_, T>> {
+    option.as_ref()?;
+    Some(Taken(option))
+}
+
+/// A constructor that can be retried in multiple containers until it succeeds.
+///
+/// It is a thin wrapper of `Option<T>` returned from [`Dynify::reusable`],
+/// which keeps the constructor if a container fails. Once the construction
+/// succeeds, the constructor is consumed, and further attempts panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut small = [MaybeUninit::<u8>::uninit(); 4];
+/// let mut large = [MaybeUninit::<u8>::uninit(); 32];
+/// let mut containers: [&mut [MaybeUninit<u8>]; 2] = [&mut small, &mut large];
+///
+/// let mut init = from_closure(|slot| slot.write([1u32; 4])).reusable();
+/// let val = containers
+///     .iter_mut()
+///     .find_map(|stack| init.try_init(&mut **stack).ok())
+///     .unwrap();
+/// assert_eq!(*val, [1; 4]);
+/// assert!(init.is_consumed());
+/// ```
+#[must_use = "constructor must be initialized"]
+#[derive(Debug)]
+pub struct Reusable<T>(Option<T>);
+impl<T: PinConstruct> Reusable<T> {
+    /// Wraps the supplied constructor.
+    pub fn new(constructor: T) -> Self {
+        Self(Some(constructor))
+    }
+
+    /// Returns whether the constructor has been consumed.
+    pub fn is_consumed(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Borrows the constructor, returning `None` if it has been consumed.
+    ///
+    /// See [`from_option`] for more details.
+    pub fn by_ref(&mut self) -> Option<Taken<'_, T>> {
+        from_option(&mut self.0)
+    }
+
+    /// Consumes this instance, returning the constructor if it has not been
+    /// consumed.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+
+    /// Constructs the object in the supplied pinned container.
+    ///
+    /// The constructor is kept if the construction fails.
+    ///
+    /// # Panic
+    ///
+    /// It panics if the constructor has been consumed.
+    pub fn try_pin_init<C>(&mut self, container: C) -> Result<Pin<C::Ptr>, C::Err>
+    where
+        C: PinEmplace<T::Object>,
+    {
+        self.taken().try_pin_init(container).map_err(|(_, e)| e)
+    }
+
+    fn taken(&mut self) -> Taken<'_, T> {
+        self.by_ref().expect("constructor has been consumed")
+    }
+}
+impl<T: Construct> Reusable<T> {
+    /// Constructs the object in the supplied container.
+    ///
+    /// The constructor is kept if the construction fails.
+    ///
+    /// # Panic
+    ///
+    /// It panics if the constructor has been consumed.
+    pub fn try_init<C>(&mut self, container: C) -> Result<C::Ptr, C::Err>
+    where
+        C: Emplace<T::Object>,
+    {
+        self.taken().try_init(container).map_err(|(_, e)| e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+unsafe impl<T: PinConstruct> PinConstruct for alloc::boxed::Box<T> {
+    type Object = T::Object;
+    fn layout(&self) -> Layout {
+        (**self).layout()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        (*self).construct(slot)
+    }
+}
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+unsafe impl<T: Construct> Construct for alloc::boxed::Box<T> {}
+
+#[cfg(feature = "either")]
+mod __either {
+    use either::Either;
+
+    use super::*;
+
+    /// Constructs the object with whichever constructor is present, so that
+    /// branches returning different constructors of the same object can be
+    /// unified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, Opaque};
+    /// # use either::Either;
+    /// # use std::fmt::Display;
+    /// let init = if rand() {
+    ///     Either::Left(from_closure(|slot| {
+    ///         slot.write(123) as &mut Opaque<dyn Display>
+    ///     }))
+    /// } else {
+    ///     Either::Right(from_closure(|slot| {
+    ///         slot.write("Hi!") as &mut Opaque<dyn Display>
+    ///     }))
+    /// };
+    /// let val = init.boxed().to_string();
+    /// assert!(val == "123" || val == "Hi!");
+    /// # fn rand() -> bool { std::time::UNIX_EPOCH.elapsed().unwrap().as_nanos() % 2 == 0 }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "either")))]
+    unsafe impl<L, R> PinConstruct for Either<L, R>
+    where
+        L: PinConstruct,
+        R: PinConstruct<Object = L::Object>,
+    {
+        type Object = L::Object;
+        fn layout(&self) -> Layout {
+            match self {
+                Either::Left(l) => l.layout(),
+                Either::Right(r) => r.layout(),
+            }
+        }
+        unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+            match self {
+                Either::Left(l) => l.construct(slot),
+                Either::Right(r) => r.construct(slot),
+            }
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "either")))]
+    unsafe impl<L, R> Construct for Either<L, R>
+    where
+        L: Construct,
+        R: Construct<Object = L::Object>,
+    {
+    }
+}
+
 /// A memory block used to store arbitrary objects.
 #[must_use = "slot must be consumed"]
 pub struct Slot<'a, T: ?Sized = Void>(NonNull<T>, PhantomData<&'a mut T>);
@@ -127,6 +392,39 @@ impl<'a> Slot<'a> {
         Self(ptr.cast(), PhantomData)
     }
 
+    /// Reserves a slot of the supplied layout in `buf`.
+    ///
+    /// The slot starts at the first address in `buf` that is aligned to
+    /// `layout`. It fails if the remaining bytes cannot hold `layout`. For
+    /// zero-sized layouts, it always succeeds with a dangling but well-aligned
+    /// slot. This is how byte buffers, such as `&mut [MaybeUninit<u8>]`,
+    /// implement [`Emplace`]. See [`Emplace`] for an example.
+    pub fn from_uninit_slice(
+        buf: &'a mut [MaybeUninit<u8>],
+        layout: Layout,
+    ) -> Result<Self, crate::OutOfCapacity> {
+        unsafe { crate::container_core::try_emplace_into(buf, layout) }
+    }
+
+    /// Allocates a slot of the supplied layout with the global allocator.
+    ///
+    /// Once an object is constructed in the returned slot, the pointer returned
+    /// from [`construct`] can be turned into a [`Box`] through
+    /// [`Box::from_raw`]. For zero-sized layouts, it returns a dangling but
+    /// well-aligned slot without allocating. If the construction panics, or
+    /// the slot is never consumed, the memory should be released with
+    /// [`dealloc`] unless `layout` is zero-sized.
+    ///
+    /// [`construct`]: PinConstruct::construct
+    /// [`Box`]: alloc::boxed::Box
+    /// [`Box::from_raw`]: alloc::boxed::Box::from_raw
+    /// [`dealloc`]: alloc::alloc::dealloc
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn from_box_layout(layout: Layout) -> Result<Self, crate::AllocError> {
+        unsafe { crate::container::box_emlace(layout).ok_or(crate::AllocError::new(layout)) }
+    }
+
     /// Consumes this slot, filling it with the supplied object.
     ///
     /// # Safety
@@ -170,6 +468,62 @@ impl<'a, T> Slot<'a, T> {
                 .as_mut()
         }
     }
+
+    /// Consumes this slot, initializing the object in place with `f`.
+    ///
+    /// Unlike [`write`](Self::write), the object is not built elsewhere before
+    /// being moved in, which avoids copying large objects through the stack.
+    ///
+    /// # Safety
+    ///
+    /// `f` must initialize the object completely.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, Slot};
+    /// # use std::ptr::addr_of_mut;
+    /// struct Large {
+    ///     len: usize,
+    ///     data: [u8; 4096],
+    /// }
+    ///
+    /// let init = from_closure(|slot: Slot<Large>| unsafe {
+    ///     slot.write_with(|uninit| {
+    ///         let ptr = uninit.as_mut_ptr();
+    ///         addr_of_mut!((*ptr).len).write(4096);
+    ///         addr_of_mut!((*ptr).data).write_bytes(0xff, 1);
+    ///     })
+    /// });
+    /// let val = init.boxed();
+    /// assert_eq!(val.len, 4096);
+    /// assert!(val.data.iter().all(|&b| b == 0xff));
+    /// ```
+    pub unsafe fn write_with<F>(mut self, f: F) -> &'a mut Opaque<T>
+    where
+        F: FnOnce(&mut MaybeUninit<T>),
+    {
+        f(self.as_uninit_mut());
+        self.assume_init()
+    }
+
+    /// Returns a mutable reference to the uninitialized object.
+    ///
+    /// Once the object is initialized, the slot can be consumed through
+    /// [`assume_init`](Self::assume_init).
+    pub fn as_uninit_mut(&mut self) -> &mut MaybeUninit<T> {
+        unsafe { self.0.cast().as_mut() }
+    }
+
+    /// Consumes this slot, assuming the object has been initialized through
+    /// [`as_uninit_mut`](Self::as_uninit_mut).
+    ///
+    /// # Safety
+    ///
+    /// The object must be initialized completely.
+    pub unsafe fn assume_init(self) -> &'a mut Opaque<T> {
+        self.0.cast().as_mut()
+    }
 }
 impl<'a, T: ?Sized> Slot<'a, T> {
     /// Consumes this instance, returning a raw pointer to the allocated memory
@@ -199,13 +553,55 @@ impl<T: ?Sized> fmt::Debug for Slot<'_, T> {
 /// [`transmute`]: core::mem::transmute
 #[repr(transparent)]
 pub struct Opaque<T: ?Sized>(T);
+impl<T> Opaque<T> {
+    /// Wraps the supplied object.
+    ///
+    /// # Safety
+    ///
+    /// Closure constructors, such as [`from_closure`], rely on the fact that
+    /// references to `Opaque` can only be obtained from [`Slot`]s, which point
+    /// to the memory blocks with the expected layouts. Therefore, references to
+    /// the returned instance may not be returned from such closures.
+    ///
+    /// [`from_closure`]: crate::from_closure
+    pub const unsafe fn new(object: T) -> Self {
+        Self(object)
+    }
+
+    /// Consumes this instance, returning the inner object.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
 impl<T: ?Sized> Opaque<T> {
-    pub(crate) fn as_mut(&mut self) -> &mut T {
+    /// Returns a mutable reference to the inner object.
+    ///
+    /// Through the returned reference, the object can only be replaced with
+    /// objects of the same type, which keeps the layout of this instance
+    /// unchanged, even if `T` is a coerced trait object.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, Opaque};
+    /// # use std::fmt::Display;
+    /// let init = from_closure(|slot| {
+    ///     let val = slot.write(String::from("Hello"));
+    ///     val.get_mut().push_str(", world!");
+    ///     val as &mut Opaque<dyn Display>
+    /// });
+    /// assert_eq!(init.boxed().to_string(), "Hello, world!");
+    /// ```
+    pub fn get_mut(&mut self) -> &mut T {
         &mut self.0
     }
 }
 
 /// The main interface used to perform in-place object constructions.
+///
+/// The layout of the object is available through [`PinConstruct::layout`]
+/// before the constructor gets consumed. See also [`PinDynify::fits`] and
+/// [`PinDynify::fits_in`] to check whether the object fits in a container.
 pub trait Dynify: Construct {
     /// Constructs the object in the supplied container.
     ///
@@ -219,7 +615,7 @@ pub trait Dynify: Construct {
         C: Emplace<Self::Object>,
     {
         self.try_init(container)
-            .unwrap_or_else(|_| panic!("failed to initialize"))
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C::available(&err)))
     }
 
     /// Constructs the object in the supplied container.
@@ -243,6 +639,144 @@ pub trait Dynify: Construct {
         }
     }
 
+    /// Constructs the object in the supplied container, panicking with a
+    /// custom message on failures.
+    ///
+    /// The panic message includes `msg`, the layout of the object and the
+    /// error returned from `container`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// # use dynify::{from_closure, Dynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+    /// let init = from_closure(|slot| slot.write([0u8; 24]));
+    /// init.expect_init(&mut stack, "stack buffer is too small");
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// It panics if `container` fails to construct the object.
+    fn expect_init<C>(self, container: C, msg: &str) -> C::Ptr
+    where
+        C: Emplace<Self::Object>,
+        C::Err: fmt::Debug,
+    {
+        self.init_or_error(container)
+            .unwrap_or_else(|e| panic!("{msg}: {e:?}"))
+    }
+
+    /// Constructs the object in the supplied container.
+    ///
+    /// It works the same as [`try_init`](Self::try_init), but wraps the
+    /// constructor and the encountered error in [`InitError`], which
+    /// implements `Error` and can thus be propagated with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+    /// let init = from_closure(|slot| slot.write([0u8; 24]));
+    /// let err = init.init_or_error(&mut stack).unwrap_err();
+    /// assert_eq!(err.layout().size(), 24);
+    /// assert_eq!(err.error().available(), 16);
+    /// let val = err.into_constructor().boxed();
+    /// assert_eq!(*val, [0; 24]);
+    /// ```
+    fn init_or_error<C>(self, container: C) -> Result<C::Ptr, InitError<Self, C::Err>>
+    where
+        C: Emplace<Self::Object>,
+    {
+        self.try_init(container).map_err(InitError::from)
+    }
+
+    /// Constructs the object in the supplied container and passes it to `f`.
+    ///
+    /// The object is dropped right after `f` returns, which makes the borrow
+    /// of `container` end as well. This is handy when a buffer is reused for
+    /// several constructions, e.g., in a loop or when the buffer is stored in
+    /// a struct alongside other fields, as no pointer to the object can be
+    /// kept around.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, Opaque};
+    /// # use std::fmt::Display;
+    /// # use std::mem::MaybeUninit;
+    /// struct Printer {
+    ///     stack: MaybeUninit<[u8; 32]>,
+    ///     lines: Vec<String>,
+    /// }
+    /// impl Printer {
+    ///     fn print(&mut self, val: impl 'static + Display) {
+    ///         let init = from_closure(|slot| slot.write(val) as &mut Opaque<dyn Display>);
+    ///         let line = init.init_scoped(&mut self.stack, |val| val.to_string());
+    ///         self.lines.push(line);
+    ///     }
+    /// }
+    ///
+    /// let mut printer = Printer {
+    ///     stack: MaybeUninit::uninit(),
+    ///     lines: Vec::new(),
+    /// };
+    /// printer.print(123);
+    /// printer.print("abc");
+    /// assert_eq!(printer.lines, ["123", "abc"]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// It panics if `container` fails to construct the object.
+    fn init_scoped<C, R, F>(self, container: C, f: F) -> R
+    where
+        C: Emplace<Self::Object>,
+        C::Ptr: core::ops::DerefMut,
+        F: FnOnce(&mut Self::Object) -> R,
+    {
+        f(&mut *self.init(container))
+    }
+
+    /// Wraps this constructor so that it can be retried in multiple
+    /// containers.
+    ///
+    /// See [`Reusable`] for more details.
+    fn reusable(self) -> Reusable<Self> {
+        Reusable::new(self)
+    }
+
+    /// Constructs the object held by `this` in the supplied container.
+    ///
+    /// The constructor is taken out of `this` only if the construction
+    /// succeeds, so that it can be retried in other containers, e.g., in a
+    /// loop. It returns `None` if `this` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut small = [MaybeUninit::<u8>::uninit(); 4];
+    /// let mut large = [MaybeUninit::<u8>::uninit(); 32];
+    /// let mut containers: [&mut [MaybeUninit<u8>]; 2] = [&mut small, &mut large];
+    ///
+    /// let mut init = Some(from_closure(|slot| slot.write([1u32; 4])));
+    /// let val = containers
+    ///     .iter_mut()
+    ///     .find_map(|stack| Dynify::try_init_from(&mut init, &mut **stack)?.ok())
+    ///     .unwrap();
+    /// assert_eq!(*val, [1; 4]);
+    /// ```
+    fn try_init_from<C>(this: &mut Option<Self>, container: C) -> Option<Result<C::Ptr, C::Err>>
+    where
+        C: Emplace<Self::Object>,
+    {
+        from_option(this).map(|init| container.emplace(init))
+    }
+
     /// Constructs the object in two containers in turn.
     ///
     /// For a non-panicking alternative see [`try_init2`](Self::try_init2).
@@ -272,7 +806,7 @@ pub trait Dynify: Construct {
         C2: Emplace<Self::Object, Ptr = P>,
     {
         self.try_init2(container1, container2)
-            .unwrap_or_else(|_| panic!("failed to initialize"))
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C2::available(&err)))
     }
 
     /// Constructs the object in two containers in turn.
@@ -288,6 +822,164 @@ pub trait Dynify: Construct {
             .or_else(|(this, _)| this.try_init(container2))
     }
 
+    /// Constructs the object in three containers in turn.
+    ///
+    /// This is useful to express fallback chains, such as stack, then
+    /// `SmallVec`, then heap. For a non-panicking alternative see
+    /// [`try_init3`](Self::try_init3).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack1 = MaybeUninit::<[u8; 4]>::uninit();
+    /// let mut stack2 = MaybeUninit::<[u8; 8]>::uninit();
+    /// let mut heap = Vec::<MaybeUninit<u8>>::new();
+    ///
+    /// let val = from_closure(|slot| slot.write([1u8; 16])).init3(&mut stack1, &mut stack2, &mut heap);
+    /// assert_eq!(*val, [1; 16]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// It panics if all containers fail to construct the object.
+    fn init3<P, C1, C2, C3>(self, container1: C1, container2: C2, container3: C3) -> P
+    where
+        C1: Emplace<Self::Object, Ptr = P>,
+        C2: Emplace<Self::Object, Ptr = P>,
+        C3: Emplace<Self::Object, Ptr = P>,
+    {
+        self.try_init3(container1, container2, container3)
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C3::available(&err)))
+    }
+
+    /// Constructs the object in three containers in turn.
+    ///
+    /// It returns the object pointer if any container succeeds. Otherwise, it
+    /// forwards the error returned from `container3`.
+    fn try_init3<P, C1, C2, C3>(
+        self,
+        container1: C1,
+        container2: C2,
+        container3: C3,
+    ) -> Result<P, (Self, C3::Err)>
+    where
+        C1: Emplace<Self::Object, Ptr = P>,
+        C2: Emplace<Self::Object, Ptr = P>,
+        C3: Emplace<Self::Object, Ptr = P>,
+    {
+        self.try_init(container1)
+            .or_else(|(this, _)| this.try_init2(container2, container3))
+    }
+
+    /// Constructs the object in two containers in turn, which may yield
+    /// different pointers.
+    ///
+    /// Unlike [`init2`](Self::init2), the pointers of both containers are
+    /// converted into [`Ptr`](crate::Ptr), so that a buffer can be paired with
+    /// [`Boxed`](crate::Boxed). For a non-panicking alternative see
+    /// [`try_init2_mixed`](Self::try_init2_mixed).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Boxed, Dynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = MaybeUninit::<[u8; 8]>::uninit();
+    /// let val = from_closure(|slot| slot.write([1u8; 16])).init2_mixed(&mut stack, Boxed);
+    /// assert!(val.is_boxed());
+    /// assert_eq!(*val, [1; 16]);
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// It panics if both containers fail to construct the object.
+    #[cfg(feature = "alloc")]
+    fn init2_mixed<'a, C1, C2>(self, container1: C1, container2: C2) -> crate::Ptr<'a, Self::Object>
+    where
+        C1: Emplace<Self::Object>,
+        C2: Emplace<Self::Object>,
+        C1::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+        C2::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+    {
+        self.try_init2_mixed(container1, container2)
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C2::available(&err)))
+    }
+
+    /// Constructs the object in two containers in turn, which may yield
+    /// different pointers.
+    ///
+    /// It returns the object pointer if either container succeeds. Otherwise,
+    /// it forwards the error returned from `container2`.
+    #[cfg(feature = "alloc")]
+    fn try_init2_mixed<'a, C1, C2>(
+        self,
+        container1: C1,
+        container2: C2,
+    ) -> Result<crate::Ptr<'a, Self::Object>, (Self, C2::Err)>
+    where
+        C1: Emplace<Self::Object>,
+        C2: Emplace<Self::Object>,
+        C1::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+        C2::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+    {
+        match self.try_init(container1) {
+            Ok(p) => Ok(p.into()),
+            Err((this, _)) => this.try_init(container2).map(Into::into),
+        }
+    }
+
+    /// Constructs the object in `container1`, or in the container returned
+    /// from `f` if that fails.
+    ///
+    /// Unlike [`init2`](Self::init2), the fallback container is only created
+    /// when it is actually needed. For a non-panicking alternative see
+    /// [`try_init_or_else`](Self::try_init_or_else).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+    /// let mut heap = None;
+    ///
+    /// let val = from_closure(|slot| slot.write([1u8; 8]))
+    ///     .init_or_else(&mut stack, || heap.insert(Vec::<MaybeUninit<u8>>::new()));
+    /// assert_eq!(*val, [1; 8]);
+    /// drop(val);
+    /// assert!(heap.is_none());
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// It panics if both containers fail to construct the object.
+    fn init_or_else<P, C1, C2, F>(self, container1: C1, f: F) -> P
+    where
+        C1: Emplace<Self::Object, Ptr = P>,
+        C2: Emplace<Self::Object, Ptr = P>,
+        F: FnOnce() -> C2,
+    {
+        self.try_init_or_else(container1, f)
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C2::available(&err)))
+    }
+
+    /// Constructs the object in `container1`, or in the container returned
+    /// from `f` if that fails.
+    ///
+    /// It returns the object pointer if either container succeeds. Otherwise,
+    /// it forwards the error returned from the fallback container.
+    fn try_init_or_else<P, C1, C2, F>(self, container1: C1, f: F) -> Result<P, (Self, C2::Err)>
+    where
+        C1: Emplace<Self::Object, Ptr = P>,
+        C2: Emplace<Self::Object, Ptr = P>,
+        F: FnOnce() -> C2,
+    {
+        self.try_init(container1)
+            .or_else(|(this, _)| this.try_init(f()))
+    }
+
     /// Constructs the object in [`Box`](alloc::boxed::Box).
     ///
     /// This function never fails as long as there is enough free memory.
@@ -295,6 +987,100 @@ pub trait Dynify: Construct {
     fn boxed(self) -> alloc::boxed::Box<Self::Object> {
         self.init(crate::container::Boxed)
     }
+
+    /// Constructs the object in [`Box`](alloc::boxed::Box).
+    ///
+    /// Unlike [`boxed`](Self::boxed), it returns `self` along with the error
+    /// if the allocation fails.
+    #[cfg(feature = "alloc")]
+    fn try_boxed(
+        self,
+    ) -> Result<alloc::boxed::Box<Self::Object>, (Self, crate::container::AllocError)> {
+        self.try_init(crate::container::TryBoxed)
+    }
+
+    /// Constructs the object in [`Box`](allocator_api2::boxed::Box) with a
+    /// custom allocator.
+    ///
+    /// For a non-panicking alternative see [`try_boxed_in`](Self::try_boxed_in).
+    ///
+    /// # Panic
+    ///
+    /// It panics if `alloc` fails to allocate memory for the object.
+    #[cfg(feature = "allocator-api2")]
+    fn boxed_in<A>(self, alloc: A) -> allocator_api2::boxed::Box<Self::Object, A>
+    where
+        A: allocator_api2::alloc::Allocator,
+    {
+        self.init(crate::container::BoxedIn(alloc))
+    }
+
+    /// Constructs the object in [`Box`](allocator_api2::boxed::Box) with a
+    /// custom allocator.
+    ///
+    /// Unlike [`boxed_in`](Self::boxed_in), it returns `self` along with the
+    /// error if the allocation fails.
+    #[cfg(feature = "allocator-api2")]
+    #[allow(clippy::type_complexity)]
+    fn try_boxed_in<A>(
+        self,
+        alloc: A,
+    ) -> Result<
+        allocator_api2::boxed::Box<Self::Object, A>,
+        (Self, allocator_api2::alloc::AllocError),
+    >
+    where
+        A: allocator_api2::alloc::Allocator,
+    {
+        self.try_init(crate::container::BoxedIn(alloc))
+    }
+
+    /// Constructs the object in [`Rc`](alloc::rc::Rc).
+    ///
+    /// This function never fails as long as there is enough free memory.
+    #[cfg(feature = "alloc")]
+    fn rc(self) -> alloc::rc::Rc<Self::Object> {
+        self.init(crate::shared::Rced)
+    }
+
+    /// Constructs the object in [`Arc`](alloc::sync::Arc).
+    ///
+    /// This function never fails as long as there is enough free memory.
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    fn arc(self) -> alloc::sync::Arc<Self::Object> {
+        self.init(crate::shared::Arced)
+    }
+
+    /// Creates a new constructor that passes the object to `f` for further
+    /// processing, such as wrapping it in another type.
+    ///
+    /// It works the same as [`from_closure`], except that `f` receives the
+    /// object constructed by `self` as well. Note that the object is built in
+    /// a temporary location before being handed over to `f`, and is therefore
+    /// moved once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, Opaque};
+    /// # use std::fmt::Debug;
+    /// #[derive(Debug)]
+    /// struct Labeled<T>(&'static str, T);
+    ///
+    /// let init = from_closure(|slot| slot.write(123))
+    ///     .map_object(|slot, val| slot.write(Labeled("answer", val)) as &mut Opaque<dyn Debug>);
+    /// assert_eq!(format!("{:?}", init.boxed()), r#"Labeled("answer", 123)"#);
+    /// ```
+    ///
+    /// [`from_closure`]: crate::from_closure
+    fn map_object<T, U, F>(self, f: F) -> crate::closure::MapObject<Self, T, F>
+    where
+        Self::Object: Sized,
+        U: ?Sized,
+        F: FnOnce(Slot<T>, Self::Object) -> &mut Opaque<U>,
+    {
+        crate::closure::map_object(self, f)
+    }
 }
 impl<T: Construct> Dynify for T {}
 
@@ -312,7 +1098,7 @@ pub trait PinDynify: PinConstruct {
         C: PinEmplace<Self::Object>,
     {
         self.try_pin_init(container)
-            .unwrap_or_else(|_| panic!("failed to initialize"))
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C::available(&err)))
     }
 
     /// Constructs the object in the supplied container.
@@ -336,6 +1122,23 @@ pub trait PinDynify: PinConstruct {
         }
     }
 
+    /// Constructs the object in the supplied container and passes it to `f`.
+    ///
+    /// This is the pinned counterpart of [`Dynify::init_scoped`]. The object
+    /// is dropped right after `f` returns.
+    ///
+    /// # Panic
+    ///
+    /// It panics if `container` fails to construct the object.
+    fn pin_init_scoped<C, R, F>(self, container: C, f: F) -> R
+    where
+        C: PinEmplace<Self::Object>,
+        C::Ptr: core::ops::DerefMut,
+        F: FnOnce(Pin<&mut Self::Object>) -> R,
+    {
+        f(self.pin_init(container).as_mut())
+    }
+
     /// Constructs the object in two containers in turn.
     ///
     /// For a non-panicking alternative see [`try_pin_init2`](Self::try_pin_init2).
@@ -349,7 +1152,7 @@ pub trait PinDynify: PinConstruct {
         C2: PinEmplace<Self::Object, Ptr = P>,
     {
         self.try_pin_init2(container1, container2)
-            .unwrap_or_else(|_| panic!("failed to initialize"))
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C2::available(&err)))
     }
 
     /// Constructs the object in two containers in turn.
@@ -369,6 +1172,165 @@ pub trait PinDynify: PinConstruct {
             .or_else(|(this, _)| this.try_pin_init(container2))
     }
 
+    /// Constructs the object in three containers in turn.
+    ///
+    /// For a non-panicking alternative see [`try_pin_init3`](Self::try_pin_init3).
+    ///
+    /// # Panic
+    ///
+    /// It panics if all containers fail to construct the object.
+    fn pin_init3<P, C1, C2, C3>(self, container1: C1, container2: C2, container3: C3) -> Pin<P>
+    where
+        C1: PinEmplace<Self::Object, Ptr = P>,
+        C2: PinEmplace<Self::Object, Ptr = P>,
+        C3: PinEmplace<Self::Object, Ptr = P>,
+    {
+        self.try_pin_init3(container1, container2, container3)
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C3::available(&err)))
+    }
+
+    /// Constructs the object in three containers in turn.
+    ///
+    /// It returns the object pointer if any container succeeds. Otherwise, it
+    /// forwards the error returned from `container3`.
+    fn try_pin_init3<P, C1, C2, C3>(
+        self,
+        container1: C1,
+        container2: C2,
+        container3: C3,
+    ) -> Result<Pin<P>, (Self, C3::Err)>
+    where
+        C1: PinEmplace<Self::Object, Ptr = P>,
+        C2: PinEmplace<Self::Object, Ptr = P>,
+        C3: PinEmplace<Self::Object, Ptr = P>,
+    {
+        self.try_pin_init(container1)
+            .or_else(|(this, _)| this.try_pin_init2(container2, container3))
+    }
+
+    /// Constructs the object in `container1`, or in the container returned
+    /// from `f` if that fails.
+    ///
+    /// For a non-panicking alternative see
+    /// [`try_pin_init_or_else`](Self::try_pin_init_or_else).
+    ///
+    /// # Panic
+    ///
+    /// It panics if both containers fail to construct the object.
+    fn pin_init_or_else<P, C1, C2, F>(self, container1: C1, f: F) -> Pin<P>
+    where
+        C1: PinEmplace<Self::Object, Ptr = P>,
+        C2: PinEmplace<Self::Object, Ptr = P>,
+        F: FnOnce() -> C2,
+    {
+        self.try_pin_init_or_else(container1, f)
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C2::available(&err)))
+    }
+
+    /// Constructs the object in `container1`, or in the container returned
+    /// from `f` if that fails.
+    ///
+    /// It returns the object pointer if either container succeeds. Otherwise,
+    /// it forwards the error returned from the fallback container.
+    fn try_pin_init_or_else<P, C1, C2, F>(
+        self,
+        container1: C1,
+        f: F,
+    ) -> Result<Pin<P>, (Self, C2::Err)>
+    where
+        C1: PinEmplace<Self::Object, Ptr = P>,
+        C2: PinEmplace<Self::Object, Ptr = P>,
+        F: FnOnce() -> C2,
+    {
+        self.try_pin_init(container1)
+            .or_else(|(this, _)| this.try_pin_init(f()))
+    }
+
+    /// Constructs the object in two containers in turn, which may yield
+    /// different pointers.
+    ///
+    /// This is the pinned counterpart of [`Dynify::init2_mixed`]. For a
+    /// non-panicking alternative see
+    /// [`try_pin_init2_mixed`](Self::try_pin_init2_mixed).
+    ///
+    /// # Panic
+    ///
+    /// It panics if both containers fail to construct the object.
+    #[cfg(feature = "alloc")]
+    fn pin_init2_mixed<'a, C1, C2>(
+        self,
+        container1: C1,
+        container2: C2,
+    ) -> Pin<crate::Ptr<'a, Self::Object>>
+    where
+        C1: PinEmplace<Self::Object>,
+        C2: PinEmplace<Self::Object>,
+        C1::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+        C2::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+    {
+        self.try_pin_init2_mixed(container1, container2)
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C2::available(&err)))
+    }
+
+    /// Constructs the object in two containers in turn, which may yield
+    /// different pointers.
+    ///
+    /// It returns the object pointer if either container succeeds. Otherwise,
+    /// it forwards the error returned from `container2`.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::type_complexity)]
+    fn try_pin_init2_mixed<'a, C1, C2>(
+        self,
+        container1: C1,
+        container2: C2,
+    ) -> Result<Pin<crate::Ptr<'a, Self::Object>>, (Self, C2::Err)>
+    where
+        C1: PinEmplace<Self::Object>,
+        C2: PinEmplace<Self::Object>,
+        C1::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+        C2::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+    {
+        fn convert<'a, T, P>(p: Pin<P>) -> Pin<crate::Ptr<'a, T>>
+        where
+            T: ?Sized,
+            P: core::ops::Deref<Target = T> + Into<crate::Ptr<'a, T>>,
+        {
+            // SAFETY: The conversion moves the pointer only, not the object.
+            unsafe { Pin::new_unchecked(Pin::into_inner_unchecked(p).into()) }
+        }
+        match self.try_pin_init(container1) {
+            Ok(p) => Ok(convert(p)),
+            Err((this, _)) => this.try_pin_init(container2).map(convert),
+        }
+    }
+
+    /// Constructs the object in `container`, or in [`Box`](alloc::boxed::Box)
+    /// if that fails.
+    ///
+    /// This makes it possible to construct `!Unpin` objects on the stack with
+    /// a heap fallback, without writing any unsafe code.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_fn, pin_buffer, Fn, PinDynify};
+    /// # use std::future::Future;
+    /// # pollster::block_on(async {
+    /// let mut stack = pin_buffer!(16);
+    /// let constructor: Fn!(=> dyn Future<Output = i32>) = from_fn!(|| async { 777 });
+    /// let ret = constructor.pin_init_or_boxed(stack.as_mut()).await;
+    /// assert_eq!(ret, 777);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn pin_init_or_boxed<'a, C>(self, container: C) -> Pin<crate::Ptr<'a, Self::Object>>
+    where
+        C: PinEmplace<Self::Object>,
+        C::Ptr: Into<crate::Ptr<'a, Self::Object>>,
+    {
+        self.pin_init2_mixed(container, crate::container::Boxed)
+    }
+
     /// Constructs the object in [`Box`](alloc::boxed::Box).
     ///
     /// This function never fails as long as there is enough free memory.
@@ -386,9 +1348,170 @@ pub trait PinDynify: PinConstruct {
     fn pin_boxed(self) -> Pin<alloc::boxed::Box<Self::Object>> {
         self.pin_init(crate::container::Boxed)
     }
+
+    /// Constructs the object in [`Box`](allocator_api2::boxed::Box) with a
+    /// custom allocator.
+    ///
+    /// # Panic
+    ///
+    /// It panics if `alloc` fails to allocate memory for the object.
+    #[cfg(feature = "allocator-api2")]
+    fn pin_boxed_in<A>(self, alloc: A) -> Pin<allocator_api2::boxed::Box<Self::Object, A>>
+    where
+        A: 'static + allocator_api2::alloc::Allocator,
+    {
+        self.pin_init(crate::container::BoxedIn(alloc))
+    }
+
+    /// Constructs the object in [`Rc`](alloc::rc::Rc).
+    ///
+    /// This function never fails as long as there is enough free memory.
+    #[cfg(feature = "alloc")]
+    fn pin_rc(self) -> Pin<alloc::rc::Rc<Self::Object>> {
+        self.pin_init(crate::shared::Rced)
+    }
+
+    /// Constructs the object in [`Arc`](alloc::sync::Arc).
+    ///
+    /// This function never fails as long as there is enough free memory.
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    fn pin_arc(self) -> Pin<alloc::sync::Arc<Self::Object>> {
+        self.pin_init(crate::shared::Arced)
+    }
+
+    /// Returns whether the object fits in a buffer of `N` bytes, regardless of
+    /// the alignment of that buffer.
+    ///
+    /// The padding required to align the object is taken into account, so this
+    /// may report `false` for buffers that happen to be aligned properly. Use
+    /// [`fits_in`](Self::fits_in) to check against a specific container.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, PinDynify};
+    /// # use std::mem::MaybeUninit;
+    /// let init = from_closure(|slot| slot.write([0u8; 12]));
+    /// if init.fits::<16>() {
+    ///     let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+    ///     let _ = init.init(&mut stack);
+    /// } else {
+    ///     let _ = init.boxed();
+    /// }
+    /// ```
+    fn fits<const N: usize>(&self) -> bool {
+        let layout = self.layout();
+        layout.size() == 0 || layout.size() + (layout.align() - 1) <= N
+    }
+
+    /// Returns whether the object can be constructed in `container`.
+    ///
+    /// This is a shorthand for [`CapacityHint::can_fit`] with the layout
+    /// returned from [`PinConstruct::layout`], which makes it possible to route
+    /// the constructor between containers before consuming it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, PinDynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+    /// let init = from_closure(|slot| slot.write([0u64; 4]));
+    /// assert!(!init.fits_in(&&mut stack));
+    /// let _ = init.boxed();
+    /// ```
+    ///
+    /// [`CapacityHint::can_fit`]: crate::CapacityHint::can_fit
+    fn fits_in<C: crate::CapacityHint>(&self, container: &C) -> bool {
+        container.can_fit(self.layout())
+    }
 }
 impl<T: PinConstruct> PinDynify for T {}
 
+/// An error returned from [`Dynify::init_or_error`].
+///
+/// It holds the constructor, which remains untouched, along with the error
+/// returned from the container.
+pub struct InitError<C, E> {
+    constructor: C,
+    error: E,
+}
+impl<C: PinConstruct, E> InitError<C, E> {
+    /// Returns the layout of the object that failed to be constructed.
+    pub fn layout(&self) -> Layout {
+        self.constructor.layout()
+    }
+}
+impl<C, E> InitError<C, E> {
+    /// Returns the error returned from the container.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Consumes this instance, returning the constructor.
+    pub fn into_constructor(self) -> C {
+        self.constructor
+    }
+
+    /// Consumes this instance, returning the constructor and the error.
+    pub fn into_parts(self) -> (C, E) {
+        (self.constructor, self.error)
+    }
+}
+impl<C, E> From<(C, E)> for InitError<C, E> {
+    fn from((constructor, error): (C, E)) -> Self {
+        Self { constructor, error }
+    }
+}
+impl<C: PinConstruct, E: fmt::Debug> fmt::Debug for InitError<C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InitError")
+            .field("layout", &self.layout())
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+impl<C: PinConstruct, E: fmt::Display> fmt::Display for InitError<C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layout = self.layout();
+        write!(
+            f,
+            "failed to initialize {} bytes aligned to {}: {}",
+            layout.size(),
+            layout.align(),
+            self.error,
+        )
+    }
+}
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<C, E> std::error::Error for InitError<C, E>
+where
+    C: PinConstruct,
+    E: 'static + std::error::Error,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cold]
+pub(crate) fn init_failed(layout: Layout, available: Option<usize>) -> ! {
+    match available {
+        Some(available) => panic!(
+            "failed to initialize {} bytes aligned to {}: only {} bytes available",
+            layout.size(),
+            layout.align(),
+            available,
+        ),
+        None => panic!(
+            "failed to initialize {} bytes aligned to {}",
+            layout.size(),
+            layout.align(),
+        ),
+    }
+}
+
 /// A utility type to reuse the inner constructor if construction fails.
 struct FallibleConstructor<T>(Option<T>);
 impl<T> FallibleConstructor<T> {
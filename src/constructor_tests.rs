@@ -3,7 +3,7 @@ use std::mem;
 use rstest::rstest;
 
 use crate::utils::*;
-use crate::{from_closure, Dynify, Emplace, PinDynify};
+use crate::{from_closure, pin_buffer, Dynify, Emplace, PinConstruct, PinDynify, Reusable, Slot};
 
 struct UnsafePinnedContainer<C>(C);
 unsafe impl<T, D> Emplace<T> for UnsafePinnedContainer<D>
@@ -42,7 +42,7 @@ fn init_ok<const N: usize>(#[case] stk_size: usize, #[case] data: [u8; N]) {
     let mut stk = newheap_fixed(stk_size);
 
     let init = from_closure(|slot| slot.write(data) as &mut OpqAny);
-    let out = init.init(&mut *stk);
+    let out = init.init(&mut stk);
     assert_eq!(out.downcast_ref::<[u8; N]>(), Some(&data));
     drop(out);
 
@@ -81,6 +81,90 @@ fn init2_ok<const N: usize>(
     assert_eq!(out.downcast_ref::<[u8; N]>(), Some(&data));
 }
 
+#[rstest]
+#[case(0, 0, 4, randarr::<4>())]
+#[case(2, 4, 5, randarr::<5>())]
+#[case(0, 8, 0, randarr::<6>())]
+#[case(6, 0, 9, randarr::<7>())]
+fn init3_ok<const N: usize>(
+    #[case] stk1_size: usize,
+    #[case] stk2_size: usize,
+    #[case] stk3_size: usize,
+    #[case] data: [u8; N],
+) {
+    let mut stk1 = newheap_fixed(stk1_size);
+    let mut stk2 = newheap_fixed(stk2_size);
+    let mut stk3 = newheap_fixed(stk3_size);
+
+    let init = from_closure(|slot| slot.write(data) as &mut OpqAny);
+    let out = init.init3(&mut *stk1, &mut *stk2, &mut *stk3);
+    assert_eq!(out.downcast_ref::<[u8; N]>(), Some(&data));
+    drop(out);
+
+    let mut stk1 = UnsafePinnedContainer(&mut *stk1);
+    let mut stk2 = UnsafePinnedContainer(&mut *stk2);
+    let mut stk3 = UnsafePinnedContainer(&mut *stk3);
+
+    let init = from_closure(|slot| slot.write(data) as &mut OpqAny);
+    let out = init.pin_init3(stk1.as_mut(), stk2.as_mut(), stk3.as_mut());
+    assert_eq!(out.downcast_ref::<[u8; N]>(), Some(&data));
+}
+
+#[rstest]
+#[case(8, 0, true)]
+#[case(4, 8, false)]
+fn init_or_else_ok(#[case] stk1_size: usize, #[case] stk2_size: usize, #[case] fast: bool) {
+    let data = randarr::<8>();
+    let mut stk1 = newheap_fixed(stk1_size);
+    let mut stk2 = newheap_fixed(stk2_size);
+
+    let mut called = false;
+    let init = from_closure(|slot| slot.write(data) as &mut OpqAny);
+    let out = init.init_or_else(&mut *stk1, || {
+        called = true;
+        &mut *stk2
+    });
+    assert_eq!(out.downcast_ref::<[u8; 8]>(), Some(&data));
+    assert_eq!(called, !fast);
+    drop(out);
+
+    let mut stk1 = UnsafePinnedContainer(&mut *stk1);
+    let mut stk2 = UnsafePinnedContainer(&mut *stk2);
+
+    let mut called = false;
+    let init = from_closure(|slot| slot.write(data) as &mut OpqAny);
+    let out = init.pin_init_or_else(stk1.as_mut(), || {
+        called = true;
+        stk2.as_mut()
+    });
+    assert_eq!(out.downcast_ref::<[u8; 8]>(), Some(&data));
+    assert_eq!(called, !fast);
+}
+
+#[test]
+#[should_panic = "failed to initialize"]
+fn panic_on_init_or_else_fail() {
+    let mut stk1 = newheap_fixed(2);
+    let mut stk2 = newheap_fixed(4);
+    let init = from_closure(|slot| slot.write(randarr::<8>()));
+    init.init_or_else(&mut *stk1, || &mut *stk2);
+}
+
+#[test]
+fn try_init3_forwards_last_error() {
+    let mut stk1 = newheap_fixed(2);
+    let mut stk2 = newheap_fixed(4);
+    let mut stk3 = newheap_fixed(6);
+
+    let init = from_closure(|slot| slot.write(randarr::<8>()) as &mut OpqAny);
+    let (init, err) = init
+        .try_init3(&mut *stk1, &mut *stk2, &mut *stk3)
+        .unwrap_err();
+    assert_eq!(err.available(), 6);
+    let mut stk4 = newheap_fixed(8);
+    assert!(init.try_init(&mut *stk4).is_ok());
+}
+
 #[rstest]
 #[case(0, 0, randarr::<7>())]
 #[case(6, 0, randarr::<7>())]
@@ -162,3 +246,246 @@ fn fallible_constructor(#[case] val: impl DebugAny) {
         assert!(init.try_init2(&mut stack, &mut heap).is_ok());
     }
 }
+
+#[test]
+fn boxed_constructor() {
+    let inp = randstr(8..16);
+    let init = Box::new(from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny));
+    assert_eq!(init.layout(), std::alloc::Layout::new::<String>());
+    let out = init.boxed();
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+}
+
+#[cfg(feature = "either")]
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn either_constructor(#[case] left: bool) {
+    use either::Either;
+
+    let inp1 = randstr(8..16);
+    let inp2 = randarr::<8>();
+    let init = if left {
+        Either::Left(from_closure(|slot| slot.write(inp1.clone()) as &mut OpqAny))
+    } else {
+        Either::Right(from_closure(|slot| slot.write(inp2) as &mut OpqAny))
+    };
+    let mut stk = newstk::<32>();
+    let out = init.init(&mut stk);
+    if left {
+        assert_eq!(out.downcast_ref::<String>(), Some(&inp1));
+    } else {
+        assert_eq!(out.downcast_ref::<[u8; 8]>(), Some(&inp2));
+    }
+}
+
+#[test]
+fn check_fits() {
+    let init = from_closure(|slot| slot.write(0u64));
+    assert!(init.fits::<15>());
+    assert!(!init.fits::<14>());
+    assert!(from_closure(|slot| slot.write(())).fits::<0>());
+
+    let mut stk = newstk::<16>();
+    assert!(init.fits_in(&&mut stk));
+    assert!(!init.fits_in(&&mut stk[..4]));
+    assert!(init.fits_in(&crate::Boxed));
+}
+
+#[test]
+fn write_slot_in_place() {
+    let data = randarr::<64>();
+    let init = from_closure(|slot: Slot<[u8; 64]>| unsafe {
+        slot.write_with(|uninit| {
+            uninit
+                .as_mut_ptr()
+                .cast::<u8>()
+                .copy_from(data.as_ptr(), 64);
+        })
+    });
+    assert_eq!(*init.boxed(), data);
+
+    let inp = randstr(8..16);
+    let init = from_closure(|mut slot| {
+        slot.as_uninit_mut().write((inp.clone(), DropCounter));
+        unsafe { slot.assume_init() as &mut OpqAny }
+    });
+    let out = init.boxed();
+    assert_eq!(out.downcast_ref::<(String, DropCounter)>().unwrap().0, inp);
+    drop(out);
+    assert_eq!(DropCounter::count(), 1);
+}
+
+#[test]
+fn unwrap_opaque() {
+    let inp = randstr(8..16);
+    let val = unsafe { crate::Opaque::new(inp.clone()) };
+    assert_eq!(val.into_inner(), inp);
+
+    let init = from_closure(|slot| {
+        let val = slot.write(inp.clone());
+        val.get_mut().push('!');
+        val as &mut OpqAny
+    });
+    let out = init.boxed();
+    assert_eq!(out.downcast_ref::<String>(), Some(&format!("{inp}!")));
+}
+
+#[test]
+fn take_option_constructor() {
+    let inp = randstr(8..16);
+    let mut stk1 = newstk::<4>();
+    let mut stk2 = newstk::<32>();
+    let mut init = Some(from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny));
+
+    let res = Dynify::try_init_from(&mut init, &mut stk1).unwrap();
+    assert!(res.is_err());
+    assert!(init.is_some());
+    let out = Dynify::try_init_from(&mut init, &mut stk2)
+        .unwrap()
+        .unwrap();
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+    drop(out);
+
+    assert!(init.is_none());
+    assert!(crate::from_option(&mut init).is_none());
+    assert!(Dynify::try_init_from(&mut init, &mut stk2).is_none());
+}
+
+#[test]
+fn reserve_slot_in_buffer() {
+    use std::alloc::Layout;
+
+    let mut stk = newstk::<16>();
+    let layout = Layout::new::<u64>();
+    let slot = Slot::from_uninit_slice(&mut stk, layout).unwrap();
+    let ptr = slot.into_raw();
+    assert!(ptr.cast::<u64>().is_aligned());
+    let range = stk.as_ptr_range();
+    assert!(range.contains(&ptr.as_ptr().cast_const().cast()));
+
+    let err = Slot::from_uninit_slice(&mut stk, Layout::new::<[u64; 3]>()).unwrap_err();
+    assert_eq!(err.available(), 16);
+
+    let slot = Slot::from_uninit_slice(&mut [], Layout::new::<[u64; 0]>()).unwrap();
+    assert_eq!(slot.into_raw().as_ptr() as usize, mem::align_of::<u64>());
+}
+
+#[test]
+fn allocate_slot_in_heap() {
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()));
+    let slot = Slot::from_box_layout(init.layout()).unwrap();
+    let out = unsafe { Box::from_raw(init.construct(slot).as_ptr()) };
+    assert_eq!(*out, inp);
+
+    let slot = Slot::from_box_layout(std::alloc::Layout::new::<()>()).unwrap();
+    assert_eq!(slot.into_raw().as_ptr() as usize, 1);
+}
+
+#[test]
+fn init_or_error_returns_constructor() {
+    let mut stk = newstk::<8>();
+    let data = randarr::<12>();
+    let init = from_closure(|slot| slot.write(data));
+    let err = init.init_or_error(&mut stk).unwrap_err();
+    assert_eq!(err.layout(), std::alloc::Layout::new::<[u8; 12]>());
+    assert_eq!(err.error().available(), 8);
+    #[cfg(feature = "std")]
+    assert!(std::error::Error::source(&err).is_some());
+    assert!(err
+        .to_string()
+        .starts_with("failed to initialize 12 bytes aligned to 1: out of capacity"));
+
+    let (init, _) = err.into_parts();
+    assert_eq!(*init.boxed(), data);
+}
+
+#[test]
+#[should_panic = "buffer is too small: InitError { layout: Layout { size: 12"]
+fn panic_on_expect_init_fail() {
+    let mut stk = newstk::<8>();
+    let init = from_closure(|slot| slot.write(randarr::<12>()));
+    init.expect_init(&mut stk, "buffer is too small");
+}
+
+#[test]
+#[should_panic = "failed to initialize 12 bytes aligned to 1: only 8 bytes available"]
+fn report_layout_on_init_fail() {
+    let mut stk = newstk::<8>();
+    let init = from_closure(|slot| slot.write(randarr::<12>()));
+    init.init(&mut stk);
+}
+
+#[test]
+#[should_panic = "failed to initialize 12 bytes aligned to 1: only 4 bytes available"]
+fn report_last_capacity_on_init2_fail() {
+    let (mut stk1, mut stk2) = (newstk::<8>(), newstk::<4>());
+    let init = from_closure(|slot| slot.write(randarr::<12>()));
+    init.init2(&mut stk1, &mut stk2);
+}
+
+#[cfg(feature = "allocator-api2")]
+#[test]
+fn init_boxed_in() {
+    use allocator_api2::alloc::Global;
+
+    let inp = randstr(8..16);
+    let out = from_closure(|slot| slot.write(inp.clone())).boxed_in(Global);
+    assert_eq!(*out, inp);
+
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let out = init.try_boxed_in(Global).ok().unwrap();
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let out = init.pin_boxed_in(Global);
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+}
+
+#[test]
+fn drop_scoped_object_on_return() {
+    let mut stk = newstk::<16>();
+    for i in 1..=3 {
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let is_counter = init.init_scoped(&mut stk, |val| val.is::<DropCounter>());
+        assert!(is_counter);
+        assert_eq!(DropCounter::count(), i);
+    }
+
+    let mut stk = pin_buffer!(16);
+    let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+    let is_counter = init.pin_init_scoped(stk.as_mut(), |val| val.is::<DropCounter>());
+    assert!(is_counter);
+    assert_eq!(DropCounter::count(), 4);
+}
+
+#[test]
+fn retry_reusable_constructor() {
+    let mut stk1 = newstk::<4>();
+    let mut stk2 = newstk::<16>();
+    let data = randarr::<8>();
+
+    let mut init = from_closure(|slot| slot.write(data) as &mut OpqAny).reusable();
+    assert!(init.try_init(&mut stk1).is_err());
+    assert!(!init.is_consumed());
+    let out = init.try_init(&mut stk2).unwrap();
+    assert_eq!(out.downcast_ref::<[u8; 8]>(), Some(&data));
+    assert!(init.is_consumed());
+    assert!(init.by_ref().is_none());
+    drop(out);
+
+    let mut stk1 = UnsafePinnedContainer(&mut stk1);
+    let mut init = Reusable::new(from_closure(|slot| slot.write(data)));
+    assert!(init.try_pin_init(stk1.as_mut()).is_err());
+    assert_eq!(*init.into_inner().unwrap().boxed(), data);
+}
+
+#[test]
+#[should_panic = "constructor has been consumed"]
+fn panic_on_reuse_consumed() {
+    let mut stk = newstk::<16>();
+    let mut init = from_closure(|slot| slot.write(randarr::<8>())).reusable();
+    let _ = init.try_init(&mut stk);
+    let _ = init.try_init(&mut stk);
+}
@@ -1,5 +1,6 @@
 use core::alloc::Layout;
 use core::any::Any;
+use core::borrow::{Borrow, BorrowMut};
 use core::fmt;
 use core::marker::PhantomData;
 use core::mem::MaybeUninit;
@@ -8,6 +9,7 @@ use core::pin::Pin;
 use core::ptr::NonNull;
 
 use crate::constructor::{Construct, PinConstruct, Slot};
+use crate::fallible::TryConstruct;
 
 /// A one-time container used for in-place constructions.
 ///
@@ -18,6 +20,64 @@ use crate::constructor::{Construct, PinConstruct, Slot};
 /// this case, the caller is responsible for preserving the provided
 /// constructor, which can be done by wrapping the constructor in [`Option`].
 ///
+/// Note that initialized byte buffers, such as `&mut [u8]`, are not containers.
+/// An object may leave uninitialized bytes (e.g., padding) in its memory block,
+/// which remain there if the returned pointer gets leaked through [`forget`].
+/// Reading such a buffer as `[u8]` afterwards is *undefined behavior*. Use
+/// `&mut [MaybeUninit<u8>]` or `&mut MaybeUninit<[u8; N]>` instead, or wrap
+/// buffers of other `Copy` elements in [`AlignedBuf`]:
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::new(0u8); 16];
+/// let val = from_closure(|slot| slot.write(123)).init(&mut stack);
+/// assert_eq!(*val, 123);
+/// ```
+///
+/// ```rust,compile_fail
+/// # use dynify::{from_closure, Dynify};
+/// let mut stack = [0u8; 16];
+/// let val = from_closure(|slot| slot.write(123)).init(&mut stack);
+/// ```
+///
+/// # Implementing containers
+///
+/// [`Slot::from_uninit_slice`] and [`Slot::from_box_layout`] handle the
+/// alignment and zero-sized layouts for buffers and heap allocations
+/// respectively, which can be used to implement custom containers:
+///
+/// ```rust
+/// # use dynify::{from_closure, Buffered, Construct, Dynify, Emplace, OutOfCapacity, Slot};
+/// # use std::mem::MaybeUninit;
+/// struct Page([MaybeUninit<u8>; 64]);
+/// unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut Page {
+///     type Ptr = Buffered<'a, T>;
+///     type Err = OutOfCapacity;
+///
+///     fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+///     where
+///         C: Construct<Object = T>,
+///     {
+///         let slot = Slot::from_uninit_slice(&mut self.0, constructor.layout())?;
+///         unsafe { Ok(Buffered::from_raw(constructor.construct(slot))) }
+///     }
+/// }
+///
+/// let mut page = Page([MaybeUninit::uninit(); 64]);
+/// let val = from_closure(|slot| slot.write(123u64)).init(&mut page);
+/// assert_eq!(*val, 123);
+/// ```
+///
+/// # Panic safety
+///
+/// If [`construct`] panics, no object is considered constructed and the panic
+/// is propagated to the caller. Containers that allocate memory for each
+/// construction, such as [`Boxed`], release it before unwinding. Buffers, such
+/// as byte slices, `Vec` and `SmallVec`, hold no object after the panic and
+/// are left in a valid state, so they are never poisoned and can be reused
+/// right away. Memory reserved by growable buffers is kept as spare capacity.
+///
 /// # Safety
 ///
 /// For the implementor,
@@ -30,6 +90,8 @@ use crate::constructor::{Construct, PinConstruct, Slot};
 ///
 /// [`construct`]: PinConstruct::construct
 /// [`emplace`]: Self::emplace
+/// [`forget`]: core::mem::forget
+/// [`Boxed`]: crate::Boxed
 pub unsafe trait Emplace<T: ?Sized>: Sized {
     type Ptr: core::ops::Deref<Target = T>;
     type Err;
@@ -42,6 +104,18 @@ pub unsafe trait Emplace<T: ?Sized>: Sized {
     fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
     where
         C: Construct<Object = T>;
+
+    /// Returns the number of bytes that were available in the container when
+    /// `err` was returned, if known.
+    ///
+    /// It is used to report failed constructions, e.g., in the panic messages
+    /// of [`Dynify::init`]. The default implementation returns `None`.
+    ///
+    /// [`Dynify::init`]: crate::Dynify::init
+    fn available(err: &Self::Err) -> Option<usize> {
+        let _ = err;
+        None
+    }
 }
 
 /// A variant of [`Emplace`] used for pinned constructions.
@@ -80,9 +154,208 @@ pub unsafe trait PinEmplace<T: ?Sized>: Emplace<T> {
     }
 }
 
+/// A container whose memory can be released between constructions.
+///
+/// Growable containers never shrink on their own, so a single large object may
+/// leave a scratch buffer oversized. This trait clears such a container and
+/// releases as much memory as possible. Since it takes an exclusive reference,
+/// it can only be called after all [`Buffered`] pointers borrowing the
+/// container are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify, Reset};
+/// # use std::mem::MaybeUninit;
+/// let mut heap = Vec::<MaybeUninit<u8>>::new();
+/// let val = from_closure(|slot| slot.write([0u8; 1024])).init(&mut heap);
+/// drop(val); // `heap` cannot be reset while `val` is alive
+/// heap.reset();
+/// assert_eq!(heap.capacity(), 0);
+/// ```
+pub trait Reset {
+    /// Clears this container and releases its unused memory.
+    fn reset(&mut self);
+}
+
+/// A container that tells whether an object fits in it.
+///
+/// This makes it possible to route constructions between containers without
+/// consuming the constructor. Containers that allocate memory on demand always
+/// report `true`, even though the allocation may fail at runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::CapacityHint;
+/// # use std::alloc::Layout;
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+/// let container = &mut stack;
+/// assert!(container.can_fit(Layout::new::<[u8; 16]>()));
+/// assert!(!container.can_fit(Layout::new::<[u8; 17]>()));
+/// ```
+pub trait CapacityHint {
+    /// Returns whether an object of `layout` can be constructed in this
+    /// container.
+    fn can_fit(&self, layout: Layout) -> bool;
+}
+
+/// A container that reserves memory ahead of constructions.
+///
+/// [`Emplace`] reserves memory and constructs the object in one step, which
+/// requires the caller to preserve the constructor if the reservation fails.
+/// With this trait, a fallible reservation is performed first, yielding a
+/// [`Reservation`] that is committed with a constructor later on. Since the
+/// constructor is only handed over once the memory is secured, it's possible to
+/// retry or fall back to other containers without wrapping the constructor in
+/// [`Option`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, PinConstruct, TryReserve};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+/// let mut heap = Vec::<MaybeUninit<u8>>::new();
+///
+/// let init = from_closure(|slot| slot.write([1u8; 32]));
+/// let layout = init.layout();
+/// let reserved = match (&mut stack[..]).reserve_slot(layout) {
+///     Ok(reserved) => reserved,
+///     Err(_) => (&mut heap).reserve_slot(layout).unwrap(),
+/// };
+/// let val = reserved.commit(init).ok().unwrap();
+/// assert_eq!(*val, [1; 32]);
+/// ```
+///
+/// # Safety
+///
+/// The returned [`Reservation`] must adhere to the contracts of
+/// [`Reservation::new`], and committing it must yield the same pointer as
+/// [`Emplace::emplace`] would do.
+pub unsafe trait TryReserve<T: ?Sized>: Emplace<T> {
+    /// Reserves a memory block of `layout` in this container.
+    ///
+    /// If the reservation fails, the container is returned along with the
+    /// error.
+    fn reserve_slot(self, layout: Layout) -> Result<Reservation<T, Self::Ptr>, (Self, Self::Err)>;
+}
+
+/// A memory block reserved by [`TryReserve`].
+///
+/// It is turned into a pointer to the constructed object through
+/// [`commit`](Self::commit). If dropped without being committed, the reserved
+/// memory block is released.
+#[must_use = "reservation must be committed"]
+pub struct Reservation<T: ?Sized, P> {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    finish: unsafe fn(NonNull<T>) -> P,
+    release: unsafe fn(NonNull<u8>, Layout),
+}
+impl<T: ?Sized, P> Reservation<T, P> {
+    /// Creates a new reservation of a memory block for objects of `layout`.
+    ///
+    /// `finish` turns a pointer to the constructed object into the returned
+    /// pointer type, and `release` releases the memory block if the
+    /// reservation is dropped or the construction panics.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must be valid for writes of `layout.size()` bytes and aligned to
+    ///   `layout.align()` until either `finish` or `release` is called.
+    /// - `finish` must be safe to call with a pointer to an object of `layout`
+    ///   constructed at `ptr`.
+    /// - `release` must be safe to call with `ptr` and `layout`.
+    pub unsafe fn new(
+        ptr: NonNull<u8>,
+        layout: Layout,
+        finish: unsafe fn(NonNull<T>) -> P,
+        release: unsafe fn(NonNull<u8>, Layout),
+    ) -> Self {
+        Self {
+            ptr,
+            layout,
+            finish,
+            release,
+        }
+    }
+
+    /// Returns the layout of the reserved memory block.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Constructs an object in the reserved memory block.
+    ///
+    /// If the layout of `constructor` differs from that of this reservation,
+    /// both of them are returned untouched.
+    pub fn commit<C>(self, constructor: C) -> Result<P, (Self, C)>
+    where
+        C: Construct<Object = T>,
+    {
+        if constructor.layout() != self.layout {
+            return Err((self, constructor));
+        }
+        let this = core::mem::ManuallyDrop::new(self);
+        let (ptr, layout) = (this.ptr, this.layout);
+        unsafe {
+            let clean_on_panic = crate::utils::defer(|| (this.release)(ptr, layout));
+            let init =
+                crate::container_core::construct_in(Slot::new_unchecked(ptr), constructor, layout);
+            core::mem::forget(clean_on_panic);
+            Ok((this.finish)(init))
+        }
+    }
+
+    /// Attempts to construct an object in the reserved memory block.
+    ///
+    /// It works the same as [`commit`](Self::commit), except that the
+    /// constructor may fail, in which case the reserved memory block is
+    /// released and the error is returned.
+    pub fn try_commit<C>(self, constructor: C) -> Result<Result<P, C::Error>, (Self, C)>
+    where
+        C: TryConstruct<Object = T>,
+    {
+        if constructor.layout() != self.layout {
+            return Err((self, constructor));
+        }
+        let this = core::mem::ManuallyDrop::new(self);
+        let (ptr, layout) = (this.ptr, this.layout);
+        unsafe {
+            let release = crate::utils::defer(|| (this.release)(ptr, layout));
+            let init = match constructor.try_construct(Slot::new_unchecked(ptr)) {
+                Ok(init) => init,
+                Err(e) => return Ok(Err(e)),
+            };
+            validate_slot(ptr, layout, init);
+            core::mem::forget(release);
+            Ok(Ok((this.finish)(init)))
+        }
+    }
+}
+impl<T: ?Sized, P> Drop for Reservation<T, P> {
+    fn drop(&mut self) {
+        unsafe { (self.release)(self.ptr, self.layout) }
+    }
+}
+impl<T: ?Sized, P> fmt::Debug for Reservation<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reservation")
+            .field("ptr", &self.ptr)
+            .field("layout", &self.layout)
+            .finish()
+    }
+}
+
+/// A no-op release function for memory blocks borrowed from buffers.
+unsafe fn release_nothing(_: NonNull<u8>, _: Layout) {}
+
 /// A pointer to objects stored in buffers.
 ///
-/// Containers such as `&mut [u8]` or `&mut Vec<u8>` yield this pointer type.
+/// Containers such as `&mut [MaybeUninit<u8>]` or `&mut Vec<MaybeUninit<u8>>`
+/// yield this pointer type.
 /// Note that, unlike most pointer types, it implements `Unpin` only if `T` is
 /// `Unpin`. While this may seem counterintuitive, it simplifies obtaining a
 /// pinned reference to `T` in safe Rust, as illustrated below:
@@ -109,7 +382,13 @@ pub unsafe trait PinEmplace<T: ?Sized>: Emplace<T> {
 ///
 /// **Tips**: `Buffered<T: Future>` implements `Future`, so you can simply write
 /// `async_hello().init(&mut stack).await` in practice.
-pub struct Buffered<'a, T: ?Sized>(NonNull<T>, PhantomData<&'a mut T>);
+///
+/// Likewise, call expressions dereference the callee, so `Buffered<dyn Fn(..)>`
+/// and `Buffered<dyn FnMut(..)>` can be called just like functions. However,
+/// calling a `dyn FnOnce(..)` requires moving it out of the buffer, which is
+/// not possible for unsized values on stable Rust. Use `dyn FnMut(..)` instead,
+/// or [`into_inner`](Self::into_inner) for sized closures.
+pub struct Buffered<'a, T: ?Sized>(NonNull<T>, PhantomData<&'a mut T>, Poison);
 impl<'a, T: ?Sized> Buffered<'a, T> {
     /// Constructs a new instance with the provided pointer.
     ///
@@ -118,7 +397,7 @@ impl<'a, T: ?Sized> Buffered<'a, T> {
     /// `ptr` must be a valid pointer to `T` and exclusive for the returned
     /// instance.
     pub unsafe fn from_raw(ptr: NonNull<T>) -> Self {
-        Self(ptr, PhantomData)
+        Self(ptr, PhantomData, Poison::default())
     }
 
     /// Consumes this instance, returning a raw pointer.
@@ -128,6 +407,62 @@ impl<'a, T: ?Sized> Buffered<'a, T> {
         ptr
     }
 
+    /// Returns the layout of the inner value.
+    ///
+    /// It is useful to find out the actual sizes of constructed objects, e.g.,
+    /// to tune the sizes of stack buffers. This is an associated function for
+    /// the same reason as [`as_ptr`](Self::as_ptr).
+    pub fn layout(this: &Self) -> Layout {
+        Layout::for_value(&**this)
+    }
+
+    /// Wraps this pointer in [`Finally`], running `hook` after the inner value
+    /// is dropped.
+    ///
+    /// [`Finally`]: crate::Finally
+    pub fn with_drop_hook<F: FnOnce()>(self, hook: F) -> crate::Finally<Self, F> {
+        crate::Finally::new(self, hook)
+    }
+
+    /// Returns a raw pointer to the inner value.
+    ///
+    /// This is an associated function to avoid conflicts with methods of the
+    /// inner value, such as [`str::as_ptr`].
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.0.as_ptr()
+    }
+
+    /// Returns a non-null pointer to the inner value.
+    ///
+    /// The pointer is valid for writes as long as this instance is not used
+    /// otherwise. This is an associated function for the same reason as
+    /// [`as_ptr`](Self::as_ptr).
+    pub fn as_non_null(this: &mut Self) -> NonNull<T> {
+        this.0
+    }
+
+    /// Returns the metadata of the pointer to the inner value, such as the
+    /// vtable of a trait object.
+    ///
+    /// It is only available on nightly Rust with `--cfg dynify_ptr_metadata`.
+    #[cfg(dynify_ptr_metadata)]
+    pub fn metadata(this: &Self) -> <T as core::ptr::Pointee>::Metadata {
+        core::ptr::metadata(this.0.as_ptr())
+    }
+
+    /// Consumes this instance, returning a mutable reference to the inner value
+    /// for the rest of the buffer's lifetime.
+    ///
+    /// Like [`Box::leak`], the inner value is never dropped, and its memory
+    /// block is released once the buffer is reused or goes out of scope. This
+    /// is an associated function for the same reason as
+    /// [`as_ptr`](Self::as_ptr).
+    ///
+    /// [`Box::leak`]: alloc::boxed::Box::leak
+    pub fn leak(this: Self) -> &'a mut T {
+        unsafe { this.into_raw().as_mut() }
+    }
+
     /// Returns a pinned mutable reference to the inner value.
     pub fn project(self: Pin<&mut Self>) -> Pin<&mut T> {
         unsafe {
@@ -143,15 +478,181 @@ impl<'a, T: ?Sized> Buffered<'a, T> {
             Pin::new_unchecked(this)
         }
     }
+
+    /// Converts the pointer to an unsized type, such as a trait object.
+    ///
+    /// `f` should perform an unsizing coercion, e.g., `|v| v as &mut dyn Any`,
+    /// since the coercion of custom pointers is not possible on stable Rust.
+    ///
+    /// # Panic
+    ///
+    /// It panics if the returned reference does not point to the same object.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Buffered, Dynify};
+    /// # use std::fmt::Display;
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+    /// let val: Buffered<i32> = from_closure(|slot| slot.write(123)).init(&mut stack);
+    /// let val: Buffered<dyn Display> = val.map_unsize(|v| v as &mut dyn Display);
+    /// assert_eq!(val.to_string(), "123");
+    /// ```
+    ///
+    /// Combined with [`downcast`], it can also convert a trait object to
+    /// another one once the concrete type is known:
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Buffered, Dynify};
+    /// # use std::any::Any;
+    /// # use std::fmt::Display;
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+    /// let init = from_closure(|slot| slot.write(123) as &mut _);
+    /// let val: Buffered<dyn Any> = init.init(&mut stack);
+    /// let val: Buffered<dyn Display> = val
+    ///     .downcast::<i32>()
+    ///     .unwrap()
+    ///     .map_unsize(|v| v as &mut dyn Display);
+    /// assert_eq!(val.to_string(), "123");
+    /// ```
+    ///
+    /// [`downcast`]: Buffered::downcast
+    pub fn map_unsize<U, F>(mut self, f: F) -> Buffered<'a, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        unsafe {
+            let layout = Layout::for_value(&*self);
+            let ptr = NonNull::from(f(&mut self));
+            assert!(
+                ptr.cast::<u8>() == self.0.cast::<u8>()
+                    && Layout::for_value(ptr.as_ref()) == layout,
+                "unsized pointer must point to the same object",
+            );
+            core::mem::forget(self);
+            Buffered::from_raw(ptr)
+        }
+    }
+
+    /// Converts the pointer to an unsized type, such as a trait object.
+    ///
+    /// It works the same as [`map_unsize`](Self::map_unsize), but relies on
+    /// the unstable `Unsize` trait instead of a closure. It is only available
+    /// on nightly Rust with `--cfg dynify_unsize`.
+    #[cfg(dynify_unsize)]
+    pub fn unsize<U: ?Sized>(self) -> Buffered<'a, U>
+    where
+        T: core::marker::Unsize<U>,
+    {
+        let ptr: NonNull<U> = self.into_raw();
+        unsafe { Buffered::from_raw(ptr) }
+    }
+
+    /// Drops the inner value and constructs a new one in its memory block.
+    ///
+    /// The new object must fit in the memory block of the current one, that
+    /// is, neither its size nor its alignment may be greater. Otherwise, both
+    /// this pointer and `constructor` are returned untouched. Note that the
+    /// memory block shrinks to the new object after each replacement. This is
+    /// an associated function for the same reason as [`as_ptr`](Self::as_ptr).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Buffered, Dynify};
+    /// # use std::fmt::Display;
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+    /// let init = from_closure(|slot| slot.write(123) as &mut _);
+    /// let val: Buffered<dyn Display> = init.init(&mut stack);
+    /// let init = from_closure(|slot| slot.write('x') as &mut _);
+    /// let val = Buffered::replace_with(val, init);
+    /// let val = val.unwrap_or_else(|_| unreachable!());
+    /// assert_eq!(val.to_string(), "x");
+    /// ```
+    pub fn replace_with<C>(this: Self, constructor: C) -> Result<Self, (Self, C)>
+    where
+        C: Construct<Object = T>,
+    {
+        let layout = constructor.layout();
+        let current = Buffered::layout(&this);
+        if layout.size() > current.size() || layout.align() > current.align() {
+            return Err((this, constructor));
+        }
+        unsafe {
+            // The memory block is merely borrowed, so there is nothing to
+            // clean up if either of the following calls panics.
+            let ptr = this.into_raw();
+            ptr.as_ptr().drop_in_place();
+            let ptr = ptr.cast::<u8>();
+            let init =
+                crate::container_core::construct_in(Slot::new_unchecked(ptr), constructor, layout);
+            Ok(Buffered::from_raw(init))
+        }
+    }
+
+    /// Returns a pinned mutable reference to the inner value without pinning
+    /// this pointer first.
+    ///
+    /// For a safe alternative see [`as_pin_mut`](Self::as_pin_mut).
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the pinning contracts of the inner value, i.e.,
+    /// it must not be moved out of this pointer, and the pointer must be
+    /// dropped rather than leaked before its memory block is reused.
+    pub unsafe fn as_pin_mut_unchecked(&mut self) -> Pin<&mut T> {
+        Pin::new_unchecked(&mut **self)
+    }
+}
+impl<T: ?Sized + Unpin> Buffered<'_, T> {
+    /// Returns a pinned mutable reference to the inner value.
+    ///
+    /// Unlike [`project`](Self::project), this pointer itself does not need to
+    /// be pinned, since `T` is `Unpin`.
+    pub fn as_pin_mut(&mut self) -> Pin<&mut T> {
+        Pin::new(&mut **self)
+    }
 }
 impl<'a, T> Buffered<'a, T> {
     /// Consumes this instance, returning the inner value.
     pub fn into_inner(self) -> T {
         unsafe { self.into_raw().read() }
     }
+
+    /// Clones the inner value into another container.
+    ///
+    /// This is an associated function for the same reason as
+    /// [`as_ptr`](Self::as_ptr).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Buffered, Dynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = [MaybeUninit::<u8>::uninit(); 32];
+    /// let mut heap = Vec::<MaybeUninit<u8>>::new();
+    /// let val = from_closure(|slot| slot.write(String::from("Hi!"))).init(&mut stack);
+    /// let cloned = Buffered::clone_into(&val, &mut heap).unwrap();
+    /// assert_eq!(*val, *cloned);
+    /// ```
+    pub fn clone_into<C>(this: &Self, container: C) -> Result<C::Ptr, C::Err>
+    where
+        T: Clone,
+        C: Emplace<T>,
+    {
+        container.emplace(crate::from_closure(|slot| slot.write(T::clone(this))))
+    }
 }
 impl<'a> Buffered<'a, dyn Any> {
     /// Attempts to downcast the pointer to a concrete type.
+    ///
+    /// To inspect the concrete type without consuming this pointer, call
+    /// `downcast_ref()` or `downcast_mut()` of the inner trait object through
+    /// auto-dereferencing.
     pub fn downcast<T: Any>(self) -> Result<Buffered<'a, T>, Self> {
         if self.is::<T>() {
             unsafe { Ok(self.downcast_unchecked()) }
@@ -173,6 +674,10 @@ impl<'a> Buffered<'a, dyn Any> {
 }
 impl<'a> Buffered<'a, dyn Any + Send> {
     /// Attempts to downcast the pointer to a concrete type.
+    ///
+    /// To inspect the concrete type without consuming this pointer, call
+    /// `downcast_ref()` or `downcast_mut()` of the inner trait object through
+    /// auto-dereferencing.
     pub fn downcast<T: Any>(self) -> Result<Buffered<'a, T>, Self> {
         if self.is::<T>() {
             unsafe { Ok(self.downcast_unchecked()) }
@@ -194,6 +699,10 @@ impl<'a> Buffered<'a, dyn Any + Send> {
 }
 impl<'a> Buffered<'a, dyn Any + Send + Sync> {
     /// Attempts to downcast the pointer to a concrete type.
+    ///
+    /// To inspect the concrete type without consuming this pointer, call
+    /// `downcast_ref()` or `downcast_mut()` of the inner trait object through
+    /// auto-dereferencing.
     pub fn downcast<T: Any>(self) -> Result<Buffered<'a, T>, Self> {
         if self.is::<T>() {
             unsafe { Ok(self.downcast_unchecked()) }
@@ -214,6 +723,76 @@ impl<'a> Buffered<'a, dyn Any + Send + Sync> {
     }
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'a> Buffered<'a, dyn std::error::Error> {
+    /// Attempts to downcast the pointer to a concrete error type.
+    pub fn downcast<E: std::error::Error + 'static>(self) -> Result<Buffered<'a, E>, Self> {
+        if self.is::<E>() {
+            unsafe { Ok(self.downcast_unchecked()) }
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Downcasts the pointer to a concrete error type.
+    ///
+    /// For a safe alternative see [`downcast`](Self::downcast).
+    ///
+    /// # Safety
+    ///
+    /// The contained value must be of type `E`.
+    pub unsafe fn downcast_unchecked<E: std::error::Error + 'static>(self) -> Buffered<'a, E> {
+        Buffered::from_raw(self.into_raw().cast())
+    }
+}
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'a> Buffered<'a, dyn std::error::Error + Send> {
+    /// Attempts to downcast the pointer to a concrete error type.
+    pub fn downcast<E: std::error::Error + 'static>(self) -> Result<Buffered<'a, E>, Self> {
+        if self.is::<E>() {
+            unsafe { Ok(self.downcast_unchecked()) }
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Downcasts the pointer to a concrete error type.
+    ///
+    /// For a safe alternative see [`downcast`](Self::downcast).
+    ///
+    /// # Safety
+    ///
+    /// The contained value must be of type `E`.
+    pub unsafe fn downcast_unchecked<E: std::error::Error + 'static>(self) -> Buffered<'a, E> {
+        Buffered::from_raw(self.into_raw().cast())
+    }
+}
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'a> Buffered<'a, dyn std::error::Error + Send + Sync> {
+    /// Attempts to downcast the pointer to a concrete error type.
+    pub fn downcast<E: std::error::Error + 'static>(self) -> Result<Buffered<'a, E>, Self> {
+        if self.is::<E>() {
+            unsafe { Ok(self.downcast_unchecked()) }
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Downcasts the pointer to a concrete error type.
+    ///
+    /// For a safe alternative see [`downcast`](Self::downcast).
+    ///
+    /// # Safety
+    ///
+    /// The contained value must be of type `E`.
+    pub unsafe fn downcast_unchecked<E: std::error::Error + 'static>(self) -> Buffered<'a, E> {
+        Buffered::from_raw(self.into_raw().cast())
+    }
+}
+
 // SAFETY: Since we hold a exclusive reference to `T`, it's okay to inherit the
 // `Send` and `Sync` bounds.
 unsafe impl<T: ?Sized + Send> Send for Buffered<'_, T> {}
@@ -231,6 +810,11 @@ unsafe impl<T: ?Sized + Sync> Sync for Buffered<'_, T> {}
 // memory region allocated to us will not be overwritten if `Buffered` is leaked
 // through `std::mem::forget`. This violation undermines the drop guarantee
 // required by `Pin`. For more information, see <https://github.com/fitzgen/bumpalo/issues/186>.
+//
+// Containers that do guarantee it, such as `PinBuffer` or `&'static
+// StaticBuffer`, implement `PinEmplace` instead, which is checked when the
+// object is constructed. A `Buffered` alone cannot tell where it comes from,
+// hence pinned pointers must be obtained through `PinDynify::pin_init`.
 impl<T: ?Sized + Unpin> Unpin for Buffered<'_, T> {}
 impl<T: ?Sized> Drop for Buffered<'_, T> {
     fn drop(&mut self) {
@@ -243,20 +827,54 @@ impl<T: ?Sized> Drop for Buffered<'_, T> {
 impl<T: ?Sized> Deref for Buffered<'_, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
+        self.2.check();
         unsafe { self.0.as_ref() }
     }
 }
 impl<T: ?Sized> DerefMut for Buffered<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.2.check();
         unsafe { self.0.as_mut() }
     }
 }
+impl<T: ?Sized> AsRef<T> for Buffered<'_, T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+impl<T: ?Sized> AsMut<T> for Buffered<'_, T> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+impl<T: ?Sized> Borrow<T> for Buffered<'_, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+impl<T: ?Sized> BorrowMut<T> for Buffered<'_, T> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
 
 impl<T: ?Sized + fmt::Debug> fmt::Debug for Buffered<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         T::fmt(self, f)
     }
 }
+impl<T: ?Sized + fmt::Display> fmt::Display for Buffered<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<T: ?Sized + std::error::Error> std::error::Error for Buffered<'_, T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        T::source(self)
+    }
+}
 
 impl<T> core::future::Future for Buffered<'_, T>
 where
@@ -267,16 +885,126 @@ where
         self: Pin<&mut Self>,
         cx: &mut core::task::Context<'_>,
     ) -> core::task::Poll<Self::Output> {
-        self.project().poll(cx)
+        let this = unsafe { Pin::into_inner_unchecked(self) };
+        this.2
+            .guard(|| unsafe { Pin::new_unchecked(this.0.as_mut()) }.poll(cx))
+    }
+}
+
+/// A type-erased future stored in a buffer.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, DynFuture, Dynify};
+/// # use std::mem::MaybeUninit;
+/// # pollster::block_on(async {
+/// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+/// let name = String::from("dynify");
+/// let init = from_closure(|slot| slot.write(async { format!("Hello, {name}!") }) as &mut _);
+/// let fut: DynFuture<String> = init.init(&mut stack);
+/// assert_eq!(fut.await, "Hello, dynify!");
+/// # });
+/// ```
+///
+/// Since `Buffered` is invariant over `T`, the lifetime of the trait object
+/// must be exactly `'a`. Spell out the full type, such as
+/// `Buffered<dyn Future<Output = O>>`, for `'static` futures stored in
+/// short-lived buffers.
+pub type DynFuture<'a, O> = Buffered<'a, dyn 'a + core::future::Future<Output = O>>;
+/// A type-erased [`Send`] future stored in a buffer.
+pub type DynSendFuture<'a, O> = Buffered<'a, dyn 'a + Send + core::future::Future<Output = O>>;
+/// A type-erased [`Any`] object stored in a buffer.
+pub type DynAny<'a> = Buffered<'a, dyn Any>;
+
+/// A flag to detect uses of objects that panicked during polling.
+///
+/// It is a no-op unless the **debug-poison** feature is enabled and debug
+/// assertions are turned on.
+#[derive(Default)]
+struct Poison(#[cfg(all(feature = "debug-poison", debug_assertions))] bool);
+impl Poison {
+    /// Panics if the object has been poisoned.
+    fn check(&self) {
+        #[cfg(all(feature = "debug-poison", debug_assertions))]
+        if self.0 {
+            panic!("object is poisoned by a panic during polling");
+        }
+    }
+
+    /// Executes `f`, poisoning the object if `f` panics.
+    fn guard<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.check();
+        #[cfg(all(feature = "debug-poison", debug_assertions))]
+        {
+            self.0 = true;
+            let ret = f();
+            self.0 = false;
+            ret
+        }
+        #[cfg(not(all(feature = "debug-poison", debug_assertions)))]
+        f()
     }
 }
 
 /// An error thrown by buffers with fixed capacity.
-#[derive(Debug)]
-pub struct OutOfCapacity;
+///
+/// It records the layout of the rejected object and the number of bytes that
+/// were available, which helps to size buffers properly.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+/// let init = from_closure(|slot| slot.write([0u8; 24]));
+/// let (_, err) = init.try_init(&mut stack).unwrap_err();
+/// assert_eq!(err.layout().size(), 24);
+/// assert_eq!(err.available(), 16);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfCapacity {
+    layout: Layout,
+    available: usize,
+}
+impl OutOfCapacity {
+    /// Creates a new error for an object of `layout` that does not fit in
+    /// `available` bytes.
+    pub const fn new(layout: Layout, available: usize) -> Self {
+        Self { layout, available }
+    }
+
+    /// Returns the layout of the rejected object.
+    pub const fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the number of bytes that were available.
+    ///
+    /// Note that alignment may take some of the available bytes, so an object
+    /// can be rejected even if its size does not exceed this number.
+    pub const fn available(&self) -> usize {
+        self.available
+    }
+}
 impl fmt::Display for OutOfCapacity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("out of capacity")
+        write!(
+            f,
+            "out of capacity: required {} bytes aligned to {}, but only {} bytes available",
+            self.layout.size(),
+            self.layout.align(),
+            self.available,
+        )
+    }
+}
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for OutOfCapacity {}
+impl From<core::convert::Infallible> for OutOfCapacity {
+    fn from(value: core::convert::Infallible) -> Self {
+        match value {}
     }
 }
 
@@ -291,6 +1019,14 @@ unsafe impl<'a, T: 'a + ?Sized, const N: usize> Emplace<T> for &'a mut MaybeUnin
         let uninit_slice: &mut [MaybeUninit<u8>; N] = unsafe { core::mem::transmute(self) };
         uninit_slice.emplace(constructor)
     }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl<const N: usize> CapacityHint for &mut MaybeUninit<[u8; N]> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        buf_can_fit(self.as_ptr().cast(), N, layout)
+    }
 }
 unsafe impl<'a, T: 'a + ?Sized, const N: usize> Emplace<T> for &'a mut [MaybeUninit<u8>; N] {
     type Ptr = Buffered<'a, T>;
@@ -302,6 +1038,14 @@ unsafe impl<'a, T: 'a + ?Sized, const N: usize> Emplace<T> for &'a mut [MaybeUni
     {
         self.as_mut_slice().emplace(constructor)
     }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl<const N: usize> CapacityHint for &mut [MaybeUninit<u8>; N] {
+    fn can_fit(&self, layout: Layout) -> bool {
+        buf_can_fit(self.as_ptr(), N, layout)
+    }
 }
 unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut [MaybeUninit<u8>] {
     type Ptr = Buffered<'a, T>;
@@ -314,49 +1058,911 @@ unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut [MaybeUninit<u8>] {
         unsafe {
             let layout = constructor.layout();
             let slot = buf_emplace(self, layout)?;
-            let ptr = slot.as_ptr();
+            let init = crate::container_core::construct_in(slot, constructor, layout);
+            Ok(Buffered::from_raw(init))
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
 
-            let init = constructor.construct(slot);
-            validate_slot(ptr, layout, init);
+unsafe impl<'a, T: 'a + ?Sized> TryReserve<T> for &'a mut [MaybeUninit<u8>] {
+    fn reserve_slot(self, layout: Layout) -> Result<Reservation<T, Self::Ptr>, (Self, Self::Err)> {
+        unsafe {
+            let bytes = core::slice::from_raw_parts_mut(self.as_mut_ptr(), self.len());
+            match buf_emplace(bytes, layout) {
+                Ok(slot) => Ok(Reservation::new(
+                    slot.as_ptr(),
+                    layout,
+                    Buffered::from_raw,
+                    release_nothing,
+                )),
+                Err(e) => Err((self, e)),
+            }
+        }
+    }
+}
+
+// `PinEmplace` is not implemented for `Pin<&mut [MaybeUninit<u8>]>`: the slice
+// is `Unpin`, so such a pinned reference can be created in safe Rust. Once a
+// `Buffered` gets leaked, the slice can be reused without running the
+// destructor, which breaks the drop guarantee required by `Pin`. `PinBuffer`
+// tracks leaked objects and should be used instead.
+impl CapacityHint for &mut [MaybeUninit<u8>] {
+    fn can_fit(&self, layout: Layout) -> bool {
+        buf_can_fit(self.as_ptr(), self.len(), layout)
+    }
+}
+
+/// A fixed-size stack buffer with guaranteed alignment.
+///
+/// Plain byte arrays are only 1-aligned, so some bytes may be wasted to align
+/// the target object. This buffer is aligned to `A`, which makes all of its `N`
+/// bytes usable for objects with a smaller or equal alignment. For instance, a
+/// `StackBuf<16>` can always hold a 16-byte future aligned to `usize`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify, StackBuf};
+/// let mut stack = StackBuf::<16, u64>::new();
+/// let val = from_closure(|slot| slot.write([1u64, 2])).init(&mut stack);
+/// assert_eq!(*val, [1, 2]);
+/// ```
+#[repr(C)]
+pub struct StackBuf<const N: usize, A = usize> {
+    _align: [A; 0],
+    buf: [MaybeUninit<u8>; N],
+}
+impl<const N: usize, A> StackBuf<N, A> {
+    /// Creates a new buffer of `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            _align: [],
+            buf: [MaybeUninit::uninit(); N],
+        }
+    }
+
+    /// Returns the number of bytes this buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+impl<const N: usize, A> Default for StackBuf<N, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize, A> fmt::Debug for StackBuf<N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackBuf")
+            .field("capacity", &N)
+            .field("align", &core::mem::align_of::<A>())
+            .finish()
+    }
+}
+
+unsafe impl<'a, T: 'a + ?Sized, const N: usize, A> Emplace<T> for &'a mut StackBuf<N, A> {
+    type Ptr = Buffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        self.buf.emplace(constructor)
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl<const N: usize, A> CapacityHint for &mut StackBuf<N, A> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        buf_can_fit(self.buf.as_ptr(), N, layout)
+    }
+}
+
+/// A stack buffer sized and aligned for a value of `R`.
+///
+/// It is typically created by [`buffer_for!`] for the return type of a
+/// function, which is computed at compile time. Constructors of that function,
+/// such as those created by [`from_fn!`], always fit in this buffer and hence
+/// never fail to be initialized in it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{buffer_for, from_fn, Dynify, Fn};
+/// # use std::future::Future;
+/// async fn read_string(path: &str) -> String {
+///     path.to_owned()
+/// }
+///
+/// let path = "/tmp/file";
+/// let mut stack = buffer_for!(read_string);
+/// let init: Fn!(_ => dyn Future<Output = String>) = from_fn!(read_string, path);
+/// let fut = init.init(&mut stack);
+/// # pollster::block_on(async {
+/// assert_eq!(fut.await, "/tmp/file");
+/// # });
+/// ```
+///
+/// [`buffer_for!`]: crate::buffer_for
+/// [`from_fn!`]: crate::from_fn
+pub struct SizedFor<R>(MaybeUninit<R>);
+impl<R> SizedFor<R> {
+    /// Creates a new buffer for a value of `R`.
+    pub const fn new() -> Self {
+        Self(MaybeUninit::uninit())
+    }
+
+    /// Returns the number of bytes this buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        core::mem::size_of::<R>()
+    }
+}
+impl<R> Default for SizedFor<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<R> fmt::Debug for SizedFor<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SizedFor")
+            .field("capacity", &self.capacity())
+            .field("align", &core::mem::align_of::<R>())
+            .finish()
+    }
+}
+
+unsafe impl<'a, T: 'a + ?Sized, R> Emplace<T> for &'a mut SizedFor<R> {
+    type Ptr = Buffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        let len = self.capacity();
+        // SAFETY: `MaybeUninit<R>` is a valid buffer of `len` bytes.
+        let buf: &mut [MaybeUninit<u8>] =
+            unsafe { core::slice::from_raw_parts_mut(self.0.as_mut_ptr().cast(), len) };
+        buf.emplace(constructor)
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl<R> CapacityHint for &mut SizedFor<R> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        buf_can_fit(self.0.as_ptr().cast(), self.capacity(), layout)
+    }
+}
+
+/// A typed container that constructs an object of `T` in place.
+///
+/// This makes it possible to initialize struct fields or stack locals with the
+/// same constructors used for trait objects. It is a wrapper rather than an
+/// implementation for `&mut MaybeUninit<T>` directly, since the latter is
+/// already a byte buffer when `T` is an array, such as `[u8; N]`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify, Uninit};
+/// # use std::mem::MaybeUninit;
+/// let mut field = MaybeUninit::<String>::uninit();
+/// let val = from_closure(|slot| slot.write(String::from("Hello!"))).init(Uninit(&mut field));
+/// assert_eq!(*val, "Hello!");
+/// ```
+#[derive(Debug)]
+pub struct Uninit<'a, T>(pub &'a mut MaybeUninit<T>);
+impl<'a, T> From<&'a mut MaybeUninit<T>> for Uninit<'a, T> {
+    fn from(value: &'a mut MaybeUninit<T>) -> Self {
+        Self(value)
+    }
+}
+
+unsafe impl<'a, T: 'a> Emplace<T> for Uninit<'a, T> {
+    type Ptr = Buffered<'a, T>;
+    type Err = core::convert::Infallible;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            // The layout of a sized object is always that of `T`.
+            let layout = constructor.layout();
+            debug_assert_eq!(layout, Layout::new::<T>());
+            let ptr = NonNull::from(self.0).cast::<u8>();
+            let slot = Slot::new_unchecked(ptr);
+
+            let init = crate::container_core::construct_in(slot, constructor, layout);
             Ok(Buffered::from_raw(init))
         }
     }
-}
-unsafe fn buf_emplace(
-    buf: &mut [MaybeUninit<u8>],
-    layout: Layout,
-) -> Result<Slot<'_>, OutOfCapacity> {
-    if layout.size() == 0 {
-        return Ok(dangling_slot(layout));
+}
+impl<T> CapacityHint for Uninit<'_, T> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        layout == Layout::new::<T>()
+    }
+}
+
+/// A container over any buffer that can be viewed as a byte slice.
+///
+/// It makes user-defined buffer types work as containers without writing an
+/// unsafe implementation of [`Emplace`] for each of them. This is a wrapper
+/// rather than a blanket implementation for `&mut B`, since the latter would
+/// conflict with containers that are byte slices as well, such as `Vec`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, AsBuffer, Dynify};
+/// # use std::mem::MaybeUninit;
+/// struct MyBuffer([MaybeUninit<u8>; 16]);
+/// impl AsMut<[MaybeUninit<u8>]> for MyBuffer {
+///     fn as_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+///         &mut self.0
+///     }
+/// }
+///
+/// let mut buf = MyBuffer([MaybeUninit::uninit(); 16]);
+/// let val = from_closure(|slot| slot.write(123)).init(AsBuffer(&mut buf));
+/// assert_eq!(*val, 123);
+/// ```
+#[derive(Debug)]
+pub struct AsBuffer<'a, B: ?Sized>(pub &'a mut B);
+impl<'a, B: ?Sized> From<&'a mut B> for AsBuffer<'a, B> {
+    fn from(value: &'a mut B) -> Self {
+        Self(value)
+    }
+}
+
+unsafe impl<'a, T, B> Emplace<T> for AsBuffer<'a, B>
+where
+    T: 'a + ?Sized,
+    B: ?Sized + AsMut<[MaybeUninit<u8>]>,
+{
+    type Ptr = Buffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        self.0.as_mut().emplace(constructor)
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl<B> CapacityHint for AsBuffer<'_, B>
+where
+    B: ?Sized + AsRef<[MaybeUninit<u8>]>,
+{
+    fn can_fit(&self, layout: Layout) -> bool {
+        let buf = (*self.0).as_ref();
+        buf_can_fit(buf.as_ptr(), buf.len(), layout)
+    }
+}
+
+/// A container over an uninitialized buffer of any `Copy` elements.
+///
+/// Byte buffers such as `[MaybeUninit<u8>; N]` are only 1-aligned, so some bytes
+/// may be wasted to align the target object. With this wrapper, buffers of
+/// other element types can be used as containers as well, such as
+/// `[MaybeUninit<usize>; N]`, which are naturally aligned to their elements.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, AlignedBuf, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u64>::uninit(); 2];
+/// let val = from_closure(|slot| slot.write(123u64)).init(AlignedBuf::from(&mut stack));
+/// assert_eq!(*val, 123);
+/// ```
+#[derive(Debug)]
+pub struct AlignedBuf<'a, U>(pub &'a mut [MaybeUninit<U>]);
+impl<'a, U: Copy> AlignedBuf<'a, U> {
+    /// Views the buffer as bytes.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice aliases the buffer, so `self` may not be used until
+    /// the slice is no longer used.
+    unsafe fn bytes(&mut self) -> &'a mut [MaybeUninit<u8>] {
+        // The elements may hold uninitialized bytes and never drop.
+        let len = core::mem::size_of_val(self.0);
+        core::slice::from_raw_parts_mut(self.0.as_mut_ptr().cast(), len)
+    }
+}
+impl<'a, U> From<&'a mut [MaybeUninit<U>]> for AlignedBuf<'a, U> {
+    fn from(value: &'a mut [MaybeUninit<U>]) -> Self {
+        Self(value)
+    }
+}
+impl<'a, U, const N: usize> From<&'a mut [MaybeUninit<U>; N]> for AlignedBuf<'a, U> {
+    fn from(value: &'a mut [MaybeUninit<U>; N]) -> Self {
+        Self(value)
+    }
+}
+impl<'a, U, const N: usize> From<&'a mut MaybeUninit<[U; N]>> for AlignedBuf<'a, U> {
+    fn from(value: &'a mut MaybeUninit<[U; N]>) -> Self {
+        // SAFETY: `MaybeUninit<[U; N]>` has the same layout as
+        // `[MaybeUninit<U>; N]`.
+        let array: &mut [MaybeUninit<U>; N] = unsafe { core::mem::transmute(value) };
+        Self(array)
+    }
+}
+
+unsafe impl<'a, T, U> Emplace<T> for AlignedBuf<'a, U>
+where
+    T: 'a + ?Sized,
+    U: Copy,
+{
+    type Ptr = Buffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(mut self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe { self.bytes() }.emplace(constructor)
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+unsafe impl<'a, T, U> TryReserve<T> for AlignedBuf<'a, U>
+where
+    T: 'a + ?Sized,
+    U: Copy,
+{
+    fn reserve_slot(
+        mut self,
+        layout: Layout,
+    ) -> Result<Reservation<T, Self::Ptr>, (Self, Self::Err)> {
+        // SAFETY: `self` is only returned if the reservation fails.
+        match unsafe { self.bytes() }.reserve_slot(layout) {
+            Ok(reserved) => Ok(reserved),
+            Err((_, e)) => Err((self, e)),
+        }
+    }
+}
+impl<U: Copy> CapacityHint for AlignedBuf<'_, U> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        buf_can_fit(
+            self.0.as_ptr().cast(),
+            core::mem::size_of_val(self.0),
+            layout,
+        )
+    }
+}
+
+/// A container over raw memory owned by the caller.
+///
+/// It makes it possible to construct objects in externally managed memory,
+/// such as blocks obtained from FFI or a custom allocator. The memory block is
+/// described by a pointer and a [`Layout`], and the bounds and alignment are
+/// checked for each construction, just like a byte slice.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify, RawContainer};
+/// # use std::alloc::{alloc, dealloc, Layout};
+/// # use std::ptr::NonNull;
+/// let layout = Layout::from_size_align(64, 16).unwrap();
+/// let ptr = NonNull::new(unsafe { alloc(layout) }).unwrap();
+///
+/// let container = unsafe { RawContainer::new(ptr, layout) };
+/// let val = from_closure(|slot| slot.write(777u128)).init(container);
+/// assert_eq!(*val, 777);
+///
+/// drop(val);
+/// unsafe { dealloc(ptr.as_ptr(), layout) };
+/// ```
+#[derive(Debug)]
+pub struct RawContainer<'a> {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    _marker: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+impl RawContainer<'_> {
+    /// Wraps the memory block starting at `ptr` and described by `layout`.
+    ///
+    /// # Safety
+    ///
+    /// The memory block must be valid for reads and writes of `layout.size()`
+    /// bytes, aligned to `layout.align()`, and not accessed through any other
+    /// pointers for the lifetime of this container, as with
+    /// [`slice::from_raw_parts_mut`].
+    ///
+    /// [`slice::from_raw_parts_mut`]: core::slice::from_raw_parts_mut
+    pub const unsafe fn new(ptr: NonNull<u8>, layout: Layout) -> Self {
+        Self {
+            ptr,
+            layout,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the layout of the memory block.
+    pub const fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for RawContainer<'a> {
+    type Ptr = Buffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        // SAFETY: Guaranteed by the caller of `RawContainer::new`.
+        let buf: &'a mut [MaybeUninit<u8>] = unsafe {
+            core::slice::from_raw_parts_mut(self.ptr.as_ptr().cast(), self.layout.size())
+        };
+        buf.emplace(constructor)
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl CapacityHint for RawContainer<'_> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        buf_can_fit(self.ptr.as_ptr().cast(), self.layout.size(), layout)
+    }
+}
+
+/// A container that falls back to another one if the first fails.
+///
+/// It works the same as [`Dynify::try_init2`], but as a single container that
+/// can be passed to APIs accepting one generic `C: Emplace`. If both containers
+/// fail, the error returned from the second one is forwarded.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, Chain, Dynify, Emplace, Fn};
+/// # use std::future::Future;
+/// # use std::mem::MaybeUninit;
+/// async fn run<C>(container: C) -> i32
+/// where
+///     C: Emplace<dyn Future<Output = i32>>,
+///     C::Ptr: Future<Output = i32>,
+/// {
+///     let constructor: Fn!(=> dyn Future<Output = i32>) = from_fn!(|| async { 777 });
+///     constructor.init(container).await
+/// }
+///
+/// # pollster::block_on(async {
+/// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+/// let mut heap = Vec::<MaybeUninit<u8>>::new();
+/// assert_eq!(run(Chain(&mut stack, &mut heap)).await, 777);
+/// # });
+/// ```
+///
+/// [`Dynify::try_init2`]: crate::Dynify::try_init2
+#[derive(Debug)]
+pub struct Chain<C1, C2>(pub C1, pub C2);
+
+unsafe impl<T, P, C1, C2> Emplace<T> for Chain<C1, C2>
+where
+    T: ?Sized,
+    P: Deref<Target = T>,
+    C1: Emplace<T, Ptr = P>,
+    C2: Emplace<T, Ptr = P>,
+{
+    type Ptr = P;
+    type Err = C2::Err;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        // The constructor remains untouched if the first container fails.
+        let mut constructor = Some(constructor);
+        match self.0.emplace(&mut constructor) {
+            Ok(p) => Ok(p),
+            Err(_) => self
+                .1
+                .emplace(constructor.expect("constructor has been consumed")),
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        C2::available(err)
+    }
+}
+unsafe impl<T, P, C1, C2> PinEmplace<T> for Chain<C1, C2>
+where
+    T: ?Sized,
+    P: Deref<Target = T>,
+    C1: PinEmplace<T, Ptr = P>,
+    C2: PinEmplace<T, Ptr = P>,
+{
+}
+impl<C1: CapacityHint, C2: CapacityHint> CapacityHint for Chain<C1, C2> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        self.0.can_fit(layout) || self.1.can_fit(layout)
+    }
+}
+
+/// A container that rejects objects larger than a maximum size.
+///
+/// It wraps another container, typically a growable one such as [`Boxed`] or
+/// `&mut Vec<MaybeUninit<u8>>`, and fails with [`OutOfCapacity`] if the target
+/// object exceeds the configured size. This helps to detect unexpectedly large
+/// objects rather than silently allocating memory for them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Bounded, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut heap = Vec::<MaybeUninit<u8>>::new();
+/// let init = from_closure(|slot| slot.write([0u8; 64]));
+/// assert!(init.try_init(Bounded::new(&mut heap, 32)).is_err());
+/// ```
+///
+/// [`Boxed`]: crate::Boxed
+#[derive(Debug)]
+pub struct Bounded<C> {
+    container: C,
+    max_size: usize,
+}
+impl<C> Bounded<C> {
+    /// Wraps `container`, limiting the size of objects to `max_size` bytes.
+    pub const fn new(container: C, max_size: usize) -> Self {
+        Self {
+            container,
+            max_size,
+        }
+    }
+
+    /// Returns the maximum size of objects.
+    pub const fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Consumes this instance, returning the inner container.
+    pub fn into_inner(self) -> C {
+        self.container
+    }
+}
+
+unsafe impl<T, C> Emplace<T> for Bounded<C>
+where
+    T: ?Sized,
+    C: Emplace<T>,
+    C::Err: Into<OutOfCapacity>,
+{
+    type Ptr = C::Ptr;
+    type Err = OutOfCapacity;
+
+    fn emplace<D>(self, constructor: D) -> Result<Self::Ptr, Self::Err>
+    where
+        D: Construct<Object = T>,
+    {
+        let layout = constructor.layout();
+        if layout.size() > self.max_size {
+            return Err(OutOfCapacity::new(layout, self.max_size));
+        }
+        self.container.emplace(constructor).map_err(Into::into)
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+unsafe impl<T, C> PinEmplace<T> for Bounded<C>
+where
+    T: ?Sized,
+    C: PinEmplace<T>,
+    C::Err: Into<OutOfCapacity>,
+{
+}
+impl<C: CapacityHint> CapacityHint for Bounded<C> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        layout.size() <= self.max_size && self.container.can_fit(layout)
+    }
+}
+
+pub(crate) unsafe fn buf_emplace(
+    buf: &mut [MaybeUninit<u8>],
+    layout: Layout,
+) -> Result<Slot<'_>, OutOfCapacity> {
+    crate::container_core::try_emplace_into(buf, layout)
+}
+pub(crate) fn buf_can_fit(start: *const MaybeUninit<u8>, len: usize, layout: Layout) -> bool {
+    crate::container_core::can_emplace_into(core::ptr::slice_from_raw_parts(start, len), layout)
+}
+
+#[cfg(feature = "alloc")]
+mod __alloc {
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+
+    use super::*;
+
+    /// A type-erased future stored in a [`Box`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub type BoxedFuture<'a, O> = Box<dyn 'a + core::future::Future<Output = O>>;
+    /// A type-erased [`Send`] future stored in a [`Box`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub type BoxedSendFuture<'a, O> = Box<dyn 'a + Send + core::future::Future<Output = O>>;
+
+    /// A unit type to perform constructions in [`Box`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[derive(Debug)]
+    pub struct Boxed;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<T: ?Sized> Emplace<T> for Boxed {
+        type Ptr = Box<T>;
+        type Err = Infallible;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            let ptr = TryBoxed
+                .emplace(constructor)
+                .unwrap_or_else(|e| alloc::alloc::handle_alloc_error(e.layout()));
+            Ok(ptr)
+        }
+    }
+    // Pinned box
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<T: ?Sized> PinEmplace<T> for Boxed {}
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    impl CapacityHint for Boxed {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+
+    /// A unit type to perform constructions in [`Box`], reporting allocation
+    /// failures as errors.
+    ///
+    /// Unlike [`Boxed`], which calls [`handle_alloc_error`] if the allocation
+    /// fails, this container returns [`AllocError`] and leaves the constructor
+    /// untouched, so that the failure can be recovered from.
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[derive(Debug)]
+    pub struct TryBoxed;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<T: ?Sized> Emplace<T> for TryBoxed {
+        type Ptr = Box<T>;
+        type Err = AllocError;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                let layout = constructor.layout();
+                let slot = box_emlace(layout).ok_or(AllocError { layout })?;
+                let ptr = slot.as_ptr();
+
+                // Recycle the allocated memory to prevent memory leaks if
+                // `construct()` panics.
+                let clean_on_panic = crate::utils::defer(|| {
+                    if layout.size() != 0 {
+                        alloc::alloc::dealloc(ptr.as_ptr(), layout)
+                    }
+                });
+                let init = crate::container_core::construct_in(slot, constructor, layout);
+
+                core::mem::forget(clean_on_panic);
+                Ok(Box::from_raw(init.as_ptr()))
+            }
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<T: ?Sized> PinEmplace<T> for TryBoxed {}
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    impl CapacityHint for TryBoxed {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<T: ?Sized> TryReserve<T> for TryBoxed {
+        fn reserve_slot(
+            self,
+            layout: Layout,
+        ) -> Result<Reservation<T, Self::Ptr>, (Self, Self::Err)> {
+            unsafe fn finish<T: ?Sized>(ptr: NonNull<T>) -> Box<T> {
+                Box::from_raw(ptr.as_ptr())
+            }
+            unsafe fn release(ptr: NonNull<u8>, layout: Layout) {
+                if layout.size() != 0 {
+                    alloc::alloc::dealloc(ptr.as_ptr(), layout)
+                }
+            }
+
+            unsafe {
+                match box_emlace(layout) {
+                    Some(slot) => Ok(Reservation::new(slot.as_ptr(), layout, finish, release)),
+                    None => Err((self, AllocError { layout })),
+                }
+            }
+        }
+    }
+    impl<T: ?Sized> Buffered<'_, T> {
+        /// Moves the inner value to a fresh heap allocation.
+        ///
+        /// This is useful when an object constructed in a buffer needs to
+        /// outlive that buffer. The object is moved bytewise, which is always
+        /// fine since it's not pinned.
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        pub fn into_boxed(self) -> Box<T> {
+            unsafe {
+                let layout = Layout::for_value(&*self);
+                let src = self.into_raw();
+                let dst = box_emlace(layout)
+                    .unwrap_or_else(|| alloc::alloc::handle_alloc_error(layout))
+                    .as_ptr();
+                core::ptr::copy_nonoverlapping(
+                    src.cast::<u8>().as_ptr(),
+                    dst.as_ptr(),
+                    layout.size(),
+                );
+                Box::from_raw(crate::utils::with_addr(src.as_ptr(), dst.as_ptr()))
+            }
+        }
+    }
+
+    pub(crate) unsafe fn box_emlace<'a>(layout: Layout) -> Option<Slot<'a>> {
+        if layout.size() == 0 {
+            return Some(dangling_slot(layout));
+        }
+        // SAFETY: `layout` is non-zero in size,
+        let ptr = NonNull::new(alloc::alloc::alloc(layout))?;
+        Some(Slot::new_unchecked(ptr))
+    }
+
+    /// An error returned when the global allocator fails to allocate memory.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct AllocError {
+        layout: Layout,
+    }
+    impl AllocError {
+        pub(crate) const fn new(layout: Layout) -> Self {
+            Self { layout }
+        }
+
+        /// Returns the layout of the failed allocation.
+        pub const fn layout(&self) -> Layout {
+            self.layout
+        }
+    }
+    impl fmt::Display for AllocError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "memory allocation of {} bytes failed",
+                self.layout.size()
+            )
+        }
+    }
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    impl std::error::Error for AllocError {}
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut Vec<MaybeUninit<u8>> {
+        type Ptr = Buffered<'a, T>;
+        type Err = Infallible;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                let layout = constructor.layout();
+                let slot = vec_emplace(self, layout);
+                let init = crate::container_core::construct_in(slot, constructor, layout);
+                Ok(Buffered::from_raw(init))
+            }
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    impl CapacityHint for &mut Vec<MaybeUninit<u8>> {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<'a, T: 'a + ?Sized> TryReserve<T> for &'a mut Vec<MaybeUninit<u8>> {
+        fn reserve_slot(
+            self,
+            layout: Layout,
+        ) -> Result<Reservation<T, Self::Ptr>, (Self, Self::Err)> {
+            unsafe {
+                let slot = vec_emplace(self, layout);
+                Ok(Reservation::new(
+                    slot.as_ptr(),
+                    layout,
+                    Buffered::from_raw,
+                    release_nothing,
+                ))
+            }
+        }
     }
+    /// A heap buffer with fixed capacity, which is the length of the slice.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut Box<[MaybeUninit<u8>]> {
+        type Ptr = Buffered<'a, T>;
+        type Err = OutOfCapacity;
 
-    let start = buf.as_mut_ptr();
-    let align_offset = start.align_offset(layout.align());
-    let total_bytes = align_offset + layout.size();
-
-    if total_bytes > buf.len() {
-        return Err(OutOfCapacity);
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            (**self).emplace(constructor)
+        }
+        fn available(err: &Self::Err) -> Option<usize> {
+            Some(err.available())
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    impl CapacityHint for &mut Box<[MaybeUninit<u8>]> {
+        fn can_fit(&self, layout: Layout) -> bool {
+            buf_can_fit(self.as_ptr(), self.len(), layout)
+        }
     }
-    let ptr = start.add(align_offset).cast::<u8>();
-    Ok(Slot::new_unchecked(NonNull::new_unchecked(ptr)))
-}
-
-#[cfg(feature = "alloc")]
-mod __alloc {
-    use alloc::boxed::Box;
-    use alloc::vec::Vec;
-    use core::convert::Infallible;
-
-    use super::*;
 
-    /// A unit type to perform constructions in [`Box`].
+    /// A container that controls how a vector grows.
+    ///
+    /// `&mut Vec<MaybeUninit<u8>>` grows in the same amortized way as
+    /// [`Vec::reserve`], which may allocate more memory than a single object
+    /// requires. This container can grow the vector to the exact capacity
+    /// instead, which suits scratch buffers that are reused for objects of the
+    /// same size. In either case, the whole capacity of the vector is reused
+    /// before it grows, regardless of its length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, VecContainer};
+    /// # use std::mem::MaybeUninit;
+    /// let mut heap = Vec::<MaybeUninit<u8>>::new();
+    /// let init = from_closure(|slot| slot.write([0u8; 24]));
+    /// let _ = init.init(VecContainer::with_exact_capacity(&mut heap));
+    /// assert_eq!(heap.capacity(), 24);
+    /// ```
     #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
     #[derive(Debug)]
-    pub struct Boxed;
+    pub struct VecContainer<'a> {
+        vec: &'a mut Vec<MaybeUninit<u8>>,
+        exact: bool,
+    }
+    impl<'a> VecContainer<'a> {
+        /// Wraps `vec`, growing it in the amortized way.
+        pub fn new(vec: &'a mut Vec<MaybeUninit<u8>>) -> Self {
+            Self { vec, exact: false }
+        }
 
-    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-    unsafe impl<T: ?Sized> Emplace<T> for Boxed {
-        type Ptr = Box<T>;
+        /// Wraps `vec`, growing it to the exact capacity required.
+        pub fn with_exact_capacity(vec: &'a mut Vec<MaybeUninit<u8>>) -> Self {
+            Self { vec, exact: true }
+        }
+    }
+
+    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for VecContainer<'a> {
+        type Ptr = Buffered<'a, T>;
         type Err = Infallible;
 
         fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
@@ -365,74 +1971,136 @@ mod __alloc {
         {
             unsafe {
                 let layout = constructor.layout();
-                let slot = box_emlace(layout);
-                let ptr = slot.as_ptr();
-
-                // Recycle the allocated memory to prevent memory leaks if
-                // `construct()` panics.
-                let clean_on_panic = crate::utils::defer(|| {
-                    if layout.size() != 0 {
-                        alloc::alloc::dealloc(ptr.as_ptr(), layout)
-                    }
-                });
-                let init = constructor.construct(slot);
-                validate_slot(ptr, layout, init);
-
-                core::mem::forget(clean_on_panic);
-                Ok(Box::from_raw(init.as_ptr()))
+                let slot = vec_emplace_with(self.vec, layout, self.exact);
+                let init = crate::container_core::construct_in(slot, constructor, layout);
+                Ok(Buffered::from_raw(init))
             }
         }
     }
-    // Pinned box
     #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-    unsafe impl<T: ?Sized> PinEmplace<T> for Boxed {}
-    unsafe fn box_emlace(layout: Layout) -> Slot<'static> {
-        if layout.size() == 0 {
-            return dangling_slot(layout);
+    impl CapacityHint for VecContainer<'_> {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
         }
-        // SAFETY: `layout` is non-zero in size,
-        let ptr = NonNull::new(alloc::alloc::alloc(layout))
-            .unwrap_or_else(|| alloc::alloc::handle_alloc_error(layout));
-        Slot::new_unchecked(ptr)
     }
 
-    // TODO: pinned vector?
+    /// Creates a container that tries `stack` first and falls back to `heap`.
+    ///
+    /// The returned container never fails since the vector grows as needed.
+    /// Hence, unlike [`Dynify::init2`], it never panics to construct objects.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_fn, Dynify, Fn};
+    /// # use std::future::Future;
+    /// # use std::mem::MaybeUninit;
+    /// # pollster::block_on(async {
+    /// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+    /// let mut heap = Vec::<MaybeUninit<u8>>::new();
+    /// let constructor: Fn!(=> dyn Future<Output = i32>) = from_fn!(|| async { 777 });
+    /// let fut = constructor.init(dynify::hybrid(&mut stack, &mut heap));
+    /// assert_eq!(fut.await, 777);
+    /// # });
+    /// ```
+    ///
+    /// [`Dynify::init2`]: crate::Dynify::init2
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn hybrid<S>(
+        stack: S,
+        heap: &mut Vec<MaybeUninit<u8>>,
+    ) -> Chain<S, &mut Vec<MaybeUninit<u8>>> {
+        Chain(stack, heap)
+    }
+
+    /// A container that places objects in the spare capacity of a vector.
+    ///
+    /// The initialized elements of the vector are left untouched, and the
+    /// vector never grows. Hence, it fails with [`OutOfCapacity`] if the spare
+    /// capacity cannot fit the object, in which case [`Vec::reserve`] may be
+    /// called beforehand.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, SpareCapacity};
+    /// let mut items = Vec::<u64>::with_capacity(8);
+    /// items.extend([1, 2, 3]);
+    /// let val = from_closure(|slot| slot.write(7u32)).init(SpareCapacity(&mut items));
+    /// assert_eq!(*val, 7);
+    /// drop(val);
+    /// assert_eq!(items, [1, 2, 3]);
+    /// ```
     #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut Vec<MaybeUninit<u8>> {
+    #[derive(Debug)]
+    pub struct SpareCapacity<'a, T>(pub &'a mut Vec<T>);
+    impl<'a, T> From<&'a mut Vec<T>> for SpareCapacity<'a, T> {
+        fn from(value: &'a mut Vec<T>) -> Self {
+            Self(value)
+        }
+    }
+
+    unsafe impl<'a, T: 'a + ?Sized, U: 'a> Emplace<T> for SpareCapacity<'a, U> {
         type Ptr = Buffered<'a, T>;
-        type Err = Infallible;
+        type Err = OutOfCapacity;
 
         fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
         where
             C: Construct<Object = T>,
         {
-            unsafe {
-                let layout = constructor.layout();
-                let slot = vec_emplace(self, layout);
-                let ptr = slot.as_ptr();
-
-                let init = constructor.construct(slot);
-                validate_slot(ptr, layout, init);
-                Ok(Buffered::from_raw(init))
-            }
+            // SAFETY: The spare capacity is uninitialized, and the vector is
+            // borrowed until the returned pointer gets dropped.
+            let spare = unsafe {
+                let spare = self.0.spare_capacity_mut();
+                let len = core::mem::size_of_val(spare);
+                let ptr = spare.as_mut_ptr().cast::<MaybeUninit<u8>>();
+                core::slice::from_raw_parts_mut(ptr, len)
+            };
+            spare.emplace(constructor)
+        }
+        fn available(err: &Self::Err) -> Option<usize> {
+            Some(err.available())
         }
     }
-    unsafe fn vec_emplace(vec: &mut Vec<MaybeUninit<u8>>, layout: Layout) -> Slot<'_> {
-        if layout.size() == 0 {
-            return dangling_slot(layout);
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    impl<T> CapacityHint for SpareCapacity<'_, T> {
+        fn can_fit(&self, layout: Layout) -> bool {
+            let len = (self.0.capacity() - self.0.len()) * core::mem::size_of::<T>();
+            let start = self.0.as_ptr().wrapping_add(self.0.len());
+            buf_can_fit(start.cast(), len, layout)
         }
+    }
 
-        let mut buf = vec.as_mut_ptr();
-        let mut align_offset = buf.align_offset(layout.align());
-        let total_bytes = align_offset + layout.size();
-
-        if total_bytes > vec.capacity() {
-            vec.reserve(layout.size() + layout.align() - vec.len());
-            buf = vec.as_mut_ptr();
-            align_offset = buf.align_offset(layout.align());
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    impl Reset for Vec<MaybeUninit<u8>> {
+        fn reset(&mut self) {
+            self.clear();
+            self.shrink_to_fit();
         }
-        let slot = buf.add(align_offset).cast::<u8>();
-        Slot::new_unchecked(NonNull::new_unchecked(slot))
+    }
+
+    pub(crate) unsafe fn vec_emplace(vec: &mut Vec<MaybeUninit<u8>>, layout: Layout) -> Slot<'_> {
+        vec_emplace_with(vec, layout, false)
+    }
+    unsafe fn vec_emplace_with(
+        vec: &mut Vec<MaybeUninit<u8>>,
+        layout: Layout,
+        exact: bool,
+    ) -> Slot<'_> {
+        crate::container_core::emplace_into(
+            |required| {
+                if let Some(required) = required {
+                    let additional = required.saturating_sub(vec.len());
+                    if exact {
+                        vec.reserve_exact(additional);
+                    } else {
+                        vec.reserve(additional);
+                    }
+                }
+                core::ptr::slice_from_raw_parts_mut(vec.as_mut_ptr(), vec.capacity())
+            },
+            layout,
+        )
     }
 }
 #[cfg(feature = "alloc")]
@@ -463,42 +2131,394 @@ mod __smallvec {
             unsafe {
                 let layout = constructor.layout();
                 let slot = small_vec_emplace(self, layout);
-                let ptr = slot.as_ptr();
-
-                let init = constructor.construct(slot);
-                validate_slot(ptr, layout, init);
+                let init = crate::container_core::construct_in(slot, constructor, layout);
                 Ok(Buffered::from_raw(init))
             }
         }
     }
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    impl<A> CapacityHint for &mut SmallVec<A>
+    where
+        A: Array<Item = MaybeUninit<u8>>,
+    {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+    /// The inline buffer is used again if it fits the remaining capacity.
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    impl<A> Reset for SmallVec<A>
+    where
+        A: Array<Item = MaybeUninit<u8>>,
+    {
+        fn reset(&mut self) {
+            self.clear();
+            self.shrink_to_fit();
+        }
+    }
+
+    /// A container over [`SmallVec`] that reports where objects are placed.
+    ///
+    /// It works the same as `&mut SmallVec`, but yields [`SmallBuffered`],
+    /// which tells whether the constructed object landed in the inline buffer
+    /// or in spilled heap storage. This helps to verify in tests that the
+    /// chosen inline capacity actually avoids heap allocations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, Dynify, Spillable};
+    /// # use smallvec::SmallVec;
+    /// # use std::mem::MaybeUninit;
+    /// let mut buf = SmallVec::<[MaybeUninit<u8>; 16]>::new();
+    /// let val = from_closure(|slot| slot.write([0u8; 8])).init(Spillable(&mut buf));
+    /// assert!(val.is_inline());
+    /// drop(val);
+    /// let val = from_closure(|slot| slot.write([0u8; 32])).init(Spillable(&mut buf));
+    /// assert!(!val.is_inline());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    pub struct Spillable<'a, A: Array>(pub &'a mut SmallVec<A>);
+    impl<'a, A: Array> From<&'a mut SmallVec<A>> for Spillable<'a, A> {
+        fn from(value: &'a mut SmallVec<A>) -> Self {
+            Self(value)
+        }
+    }
+    impl<A: Array> fmt::Debug for Spillable<'_, A> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Spillable")
+                .field("capacity", &self.0.capacity())
+                .field("spilled", &self.0.spilled())
+                .finish()
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    unsafe impl<'a, A, T> Emplace<T> for Spillable<'a, A>
+    where
+        A: Array<Item = MaybeUninit<u8>>,
+        T: 'a + ?Sized,
+    {
+        type Ptr = SmallBuffered<'a, T>;
+        type Err = Infallible;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                let layout = constructor.layout();
+                let slot = small_vec_emplace(&mut *self.0, layout);
+                let init = crate::container_core::construct_in(slot, constructor, layout);
+                // ZSTs are never stored in the heap.
+                let inline = layout.size() == 0 || !self.0.spilled();
+                Ok(SmallBuffered {
+                    ptr: Buffered::from_raw(init),
+                    inline,
+                })
+            }
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    impl<A> CapacityHint for Spillable<'_, A>
+    where
+        A: Array<Item = MaybeUninit<u8>>,
+    {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+
+    /// A pointer to objects stored in [`SmallVec`], yielded by [`Spillable`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    pub struct SmallBuffered<'a, T: ?Sized> {
+        ptr: Buffered<'a, T>,
+        inline: bool,
+    }
+    impl<'a, T: ?Sized> SmallBuffered<'a, T> {
+        /// Returns `true` if the object is stored in the inline buffer, i.e.,
+        /// no heap allocation is required to construct it.
+        pub fn is_inline(&self) -> bool {
+            self.inline
+        }
+
+        /// Consumes this instance, returning the underlying [`Buffered`].
+        pub fn into_buffered(self) -> Buffered<'a, T> {
+            self.ptr
+        }
+    }
+    impl<T: ?Sized> Deref for SmallBuffered<'_, T> {
+        type Target = T;
+        fn deref(&self) -> &Self::Target {
+            &self.ptr
+        }
+    }
+    impl<T: ?Sized> DerefMut for SmallBuffered<'_, T> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.ptr
+        }
+    }
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for SmallBuffered<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            T::fmt(self, f)
+        }
+    }
+
     unsafe fn small_vec_emplace<A>(vec: &mut SmallVec<A>, layout: Layout) -> Slot<'_>
     where
         A: Array<Item = MaybeUninit<u8>>,
     {
+        crate::container_core::emplace_into(
+            |required| {
+                if let Some(required) = required {
+                    vec.reserve(required.saturating_sub(vec.len()));
+                }
+                core::ptr::slice_from_raw_parts_mut(vec.as_mut_ptr(), vec.capacity())
+            },
+            layout,
+        )
+    }
+}
+#[cfg(feature = "smallvec")]
+pub use __smallvec::*;
+
+#[cfg(feature = "bumpalo")]
+mod __bumpalo {
+    use bumpalo::{AllocErr, Bump};
+
+    use super::*;
+
+    // `PinEmplace` is not implemented for `&Bump`: once a `Buffered` gets
+    // leaked, the arena can be reset and the memory reused without running
+    // the destructor, which breaks the drop guarantee required by `Pin`. For
+    // more information, see <https://github.com/fitzgen/bumpalo/issues/186>.
+    #[cfg_attr(docsrs, doc(cfg(feature = "bumpalo")))]
+    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a Bump {
+        type Ptr = Buffered<'a, T>;
+        type Err = AllocErr;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                let layout = constructor.layout();
+                let slot = bump_emplace(self, layout)?;
+                let init = crate::container_core::construct_in(slot, constructor, layout);
+                Ok(Buffered::from_raw(init))
+            }
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "bumpalo")))]
+    impl CapacityHint for &Bump {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+    unsafe fn bump_emplace(bump: &Bump, layout: Layout) -> Result<Slot<'_>, AllocErr> {
         if layout.size() == 0 {
-            return dangling_slot(layout);
+            return Ok(dangling_slot(layout));
+        }
+        let ptr = bump.try_alloc_layout(layout)?;
+        Ok(Slot::new_unchecked(ptr))
+    }
+}
+
+#[cfg(feature = "allocator-api2")]
+mod __allocator_api2 {
+    use allocator_api2::alloc::{AllocError, Allocator};
+    use allocator_api2::boxed::Box;
+
+    use super::*;
+
+    /// A container to perform constructions in [`Box`] with a custom
+    /// allocator.
+    ///
+    /// Unlike [`Boxed`], allocation failures are reported as errors rather than
+    /// aborting the program.
+    ///
+    /// [`Boxed`]: crate::Boxed
+    #[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+    #[derive(Debug)]
+    pub struct BoxedIn<A>(pub A);
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+    unsafe impl<T: ?Sized, A: Allocator> Emplace<T> for BoxedIn<A> {
+        type Ptr = Box<T, A>;
+        type Err = AllocError;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                let alloc = self.0;
+                let layout = constructor.layout();
+                // Zero-sized allocations are delegated to the allocator as
+                // well, since `Box` always deallocates its memory on drop.
+                let ptr = alloc.allocate(layout)?.cast::<u8>();
+                let slot = Slot::new_unchecked(ptr);
+
+                // Recycle the allocated memory to prevent memory leaks if
+                // `construct()` panics.
+                let clean_on_panic = crate::utils::defer(|| alloc.deallocate(ptr, layout));
+                let init = crate::container_core::construct_in(slot, constructor, layout);
+
+                core::mem::forget(clean_on_panic);
+                Ok(Box::from_raw_in(init.as_ptr(), alloc))
+            }
+        }
+    }
+    // Pinned box, see `allocator_api2::boxed::Box::into_pin`
+    #[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+    unsafe impl<T: ?Sized, A: 'static + Allocator> PinEmplace<T> for BoxedIn<A> {}
+    #[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+    impl<A: Allocator> CapacityHint for BoxedIn<A> {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+}
+#[cfg(feature = "allocator-api2")]
+pub use __allocator_api2::*;
+
+#[cfg(feature = "arrayvec")]
+mod __arrayvec {
+    use arrayvec::ArrayVec;
+
+    use super::*;
+
+    /// Objects are placed in the spare capacity of the vector, leaving its
+    /// existing elements untouched.
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+    unsafe impl<'a, T: 'a + ?Sized, const N: usize> Emplace<T> for &'a mut ArrayVec<u8, N> {
+        type Ptr = Buffered<'a, T>;
+        type Err = OutOfCapacity;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            // SAFETY: Bytes beyond the length of the vector are uninitialized,
+            // and `self` is borrowed until the returned pointer gets dropped.
+            let spare = unsafe {
+                let len = self.len();
+                let ptr = self.as_mut_ptr().add(len).cast::<MaybeUninit<u8>>();
+                core::slice::from_raw_parts_mut(ptr, N - len)
+            };
+            spare.emplace(constructor)
+        }
+        fn available(err: &Self::Err) -> Option<usize> {
+            Some(err.available())
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+    impl<const N: usize> CapacityHint for &mut ArrayVec<u8, N> {
+        fn can_fit(&self, layout: Layout) -> bool {
+            let start = self.as_ptr().wrapping_add(self.len());
+            buf_can_fit(start.cast(), N - self.len(), layout)
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod __bytes {
+    use core::convert::Infallible;
+
+    use bytes::BytesMut;
+
+    use super::*;
+
+    /// Objects are placed in the spare capacity of the buffer, which is
+    /// reserved as needed, leaving its existing contents untouched.
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut BytesMut {
+        type Ptr = Buffered<'a, T>;
+        type Err = Infallible;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                let layout = constructor.layout();
+                let slot = crate::container_core::emplace_into(
+                    |required| {
+                        if let Some(required) = required {
+                            self.reserve(required);
+                        }
+                        core::ptr::from_mut(self.spare_capacity_mut())
+                    },
+                    layout,
+                );
+                let init = crate::container_core::construct_in(slot, constructor, layout);
+                Ok(Buffered::from_raw(init))
+            }
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+    impl CapacityHint for &mut BytesMut {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+mod __futures {
+    use core::task::{Context, Poll};
+
+    use futures_core::future::FusedFuture;
+    use futures_core::stream::{FusedStream, Stream};
+
+    use super::*;
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    impl<T: ?Sized + FusedFuture> FusedFuture for Buffered<'_, T> {
+        fn is_terminated(&self) -> bool {
+            T::is_terminated(self)
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    impl<T: ?Sized + Stream> Stream for Buffered<'_, T> {
+        type Item = T::Item;
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = unsafe { Pin::into_inner_unchecked(self) };
+            this.2
+                .guard(|| unsafe { Pin::new_unchecked(this.0.as_mut()) }.poll_next(cx))
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            T::size_hint(self)
+        }
+    }
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    impl<T: ?Sized + FusedStream> FusedStream for Buffered<'_, T> {
+        fn is_terminated(&self) -> bool {
+            T::is_terminated(self)
         }
+    }
+}
 
-        let mut buf = vec.as_mut_ptr();
-        let mut align_offset = buf.align_offset(layout.align());
-        let total_bytes = align_offset + layout.size();
+#[cfg(feature = "serde")]
+mod __serde {
+    use serde::{Serialize, Serializer};
+
+    use super::*;
 
-        if total_bytes > vec.capacity() {
-            vec.reserve(layout.size() + layout.align() - vec.len());
-            buf = vec.as_mut_ptr();
-            align_offset = buf.align_offset(layout.align());
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    impl<T: ?Sized + Serialize> Serialize for Buffered<'_, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            T::serialize(self, serializer)
         }
-        let slot = buf.add(align_offset).cast::<u8>();
-        Slot::new_unchecked(NonNull::new_unchecked(slot))
     }
 }
 
 // TODO: is it possible to use strict provenance APIs?
-unsafe fn dangling_slot<'a>(layout: Layout) -> Slot<'a> {
+pub(crate) unsafe fn dangling_slot<'a>(layout: Layout) -> Slot<'a> {
     Slot::new_unchecked(NonNull::new_unchecked(layout.align() as *mut u8))
 }
 
-fn validate_slot<T: ?Sized>(ptr: NonNull<u8>, layout: Layout, init: NonNull<T>) {
+pub(crate) fn validate_slot<T: ?Sized>(ptr: NonNull<u8>, layout: Layout, init: NonNull<T>) {
     if cfg!(debug_assertions) {
         let init_ptr = init.cast::<u8>();
         assert_eq!(init_ptr, ptr, "initialized address mismatches");
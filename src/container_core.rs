@@ -0,0 +1,216 @@
+//! Low-level building blocks of containers.
+//!
+//! Containers of this crate are built on top of these functions, which place
+//! objects at aligned addresses, handle zero-sized layouts and check
+//! constructed objects in debug builds. Custom containers may use them as
+//! well to stay consistent with the built-in ones. For the high-level helpers,
+//! see [`Slot::from_uninit_slice`] and [`Slot::from_box_layout`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use dynify::container_core::{construct_in, emplace_into};
+//! # use dynify::{from_closure, Buffered, Construct, Dynify, Emplace};
+//! # use std::convert::Infallible;
+//! # use std::mem::MaybeUninit;
+//! # use std::ptr;
+//! struct Growable(Vec<MaybeUninit<u8>>);
+//! unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut Growable {
+//!     type Ptr = Buffered<'a, T>;
+//!     type Err = Infallible;
+//!
+//!     fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+//!     where
+//!         C: Construct<Object = T>,
+//!     {
+//!         let vec = &mut self.0;
+//!         let layout = constructor.layout();
+//!         unsafe {
+//!             let slot = emplace_into(
+//!                 |required| {
+//!                     if let Some(required) = required {
+//!                         vec.reserve(required.saturating_sub(vec.len()));
+//!                     }
+//!                     ptr::slice_from_raw_parts_mut(vec.as_mut_ptr(), vec.capacity())
+//!                 },
+//!                 layout,
+//!             );
+//!             Ok(Buffered::from_raw(construct_in(slot, constructor, layout)))
+//!         }
+//!     }
+//! }
+//!
+//! let mut heap = Growable(Vec::new());
+//! let val = from_closure(|slot| slot.write([7u64; 4])).init(&mut heap);
+//! assert_eq!(*val, [7; 4]);
+//! ```
+//!
+//! [`Slot::from_box_layout`]: crate::Slot::from_box_layout
+
+use core::alloc::Layout;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use crate::constructor::{PinConstruct, Slot};
+use crate::container::{dangling_slot, validate_slot, OutOfCapacity};
+
+/// Reserves a slot of the supplied layout in `block`.
+///
+/// The slot starts at the first address in `block` that is aligned to
+/// `layout`. It fails if the remaining bytes cannot hold `layout`. For
+/// zero-sized layouts, it always succeeds with a dangling but well-aligned
+/// slot.
+///
+/// # Safety
+///
+/// `block` must be valid for writes and exclusive for `'a`.
+pub unsafe fn try_emplace_into<'a>(
+    block: *mut [MaybeUninit<u8>],
+    layout: Layout,
+) -> Result<Slot<'a>, OutOfCapacity> {
+    if layout.size() == 0 {
+        return Ok(dangling_slot(layout));
+    }
+
+    let start = block.cast::<MaybeUninit<u8>>();
+    let align_offset =
+        offset_in(block, layout).ok_or_else(|| OutOfCapacity::new(layout, block.len()))?;
+    let ptr = start.add(align_offset).cast::<u8>();
+    Ok(Slot::new_unchecked(NonNull::new_unchecked(ptr)))
+}
+
+/// Returns `true` if [`try_emplace_into`] would succeed with the same
+/// arguments.
+///
+/// Unlike [`try_emplace_into`], it never accesses `block`, hence it is safe to
+/// call with any block.
+pub fn can_emplace_into(block: *const [MaybeUninit<u8>], layout: Layout) -> bool {
+    layout.size() == 0 || offset_in(block, layout).is_some()
+}
+
+/// Returns the offset of the first address in `block` that is aligned to
+/// `layout`, if the remaining bytes can hold `layout`.
+fn offset_in(block: *const [MaybeUninit<u8>], layout: Layout) -> Option<usize> {
+    let align_offset = block.cast::<MaybeUninit<u8>>().align_offset(layout.align());
+    // `align_offset` may be `usize::MAX` if the address cannot be aligned.
+    if align_offset.saturating_add(layout.size()) > block.len() {
+        return None;
+    }
+    Some(align_offset)
+}
+
+/// Reserves a slot of the supplied layout in a growable buffer.
+///
+/// `buf` is first invoked with `None` to obtain the current memory block of
+/// the buffer. If that block cannot hold `layout`, it is invoked again with
+/// the number of bytes required in the worst case, i.e., when the new block is
+/// not aligned at all, and it must then grow the buffer to at least that size.
+/// See [`try_emplace_into`] for how the slot is placed.
+///
+/// # Safety
+///
+/// The returned blocks must be valid for writes and exclusive for `'a`.
+///
+/// # Panic
+///
+/// It panics if the grown block is smaller than required.
+pub unsafe fn emplace_into<'a, F>(mut buf: F, layout: Layout) -> Slot<'a>
+where
+    F: FnMut(Option<usize>) -> *mut [MaybeUninit<u8>],
+{
+    if let Ok(slot) = try_emplace_into(buf(None), layout) {
+        return slot;
+    }
+    let required = layout.size() + layout.align() - 1;
+    try_emplace_into(buf(Some(required)), layout)
+        .unwrap_or_else(|_| panic!("grown buffer must fit the layout"))
+}
+
+/// Constructs the object of `constructor` in `slot`.
+///
+/// `layout` must be the one used to reserve `slot`. In debug builds, it checks
+/// that the object is constructed at the address of `slot` with `layout`.
+///
+/// # Safety
+///
+/// See the safety notes of [`PinConstruct::construct`].
+pub unsafe fn construct_in<C>(slot: Slot, constructor: C, layout: Layout) -> NonNull<C::Object>
+where
+    C: PinConstruct,
+{
+    let ptr = slot.as_ptr();
+    let init = constructor.construct(slot);
+    validate_slot(ptr, layout, init);
+    init
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+    use crate::utils::newstk;
+
+    #[test]
+    fn place_slot_at_aligned_address() {
+        let mut stk = newstk::<32>();
+        let block = ptr::from_mut(&mut stk[1..]);
+        let slot = unsafe { try_emplace_into(block, Layout::new::<u64>()) }.unwrap();
+        let ptr = slot.into_raw();
+        assert!(ptr.cast::<u64>().is_aligned());
+        assert!(stk
+            .as_ptr_range()
+            .contains(&ptr.as_ptr().cast_const().cast()));
+
+        let err = unsafe { try_emplace_into(block, Layout::new::<[u64; 4]>()) }.unwrap_err();
+        assert_eq!(err.available(), 31);
+
+        let slot = unsafe { try_emplace_into(&mut [], Layout::new::<[u32; 0]>()) }.unwrap();
+        assert_eq!(
+            slot.into_raw().as_ptr() as usize,
+            core::mem::align_of::<u32>()
+        );
+    }
+
+    #[test]
+    fn check_slot_placement() {
+        let mut stk = newstk::<32>();
+        let block = ptr::from_mut(&mut stk[1..]);
+        assert!(can_emplace_into(block, Layout::new::<[u64; 3]>()));
+        assert!(!can_emplace_into(block, Layout::new::<[u64; 4]>()));
+        assert!(can_emplace_into(&[], Layout::new::<[u32; 0]>()));
+
+        let huge = Layout::from_size_align(isize::MAX as usize, 1).unwrap();
+        assert!(!can_emplace_into(block, huge));
+    }
+
+    #[test]
+    fn grow_buffer_on_demand() {
+        let mut vec = Vec::<MaybeUninit<u8>>::new();
+        let mut requests = Vec::new();
+        let layout = Layout::new::<[u64; 2]>();
+        let slot = unsafe {
+            emplace_into(
+                |required| {
+                    requests.push(required);
+                    if let Some(required) = required {
+                        vec.reserve_exact(required);
+                    }
+                    ptr::slice_from_raw_parts_mut(vec.as_mut_ptr(), vec.capacity())
+                },
+                layout,
+            )
+        };
+        assert!(slot.into_raw().cast::<[u64; 2]>().is_aligned());
+        assert_eq!(requests, [None, Some(16 + 8 - 1)]);
+    }
+
+    #[test]
+    #[should_panic = "grown buffer must fit the layout"]
+    fn panic_on_insufficient_growth() {
+        let mut stk = newstk::<4>();
+        let block = ptr::from_mut(&mut stk[..]);
+        let _ = unsafe { emplace_into(|_| block, Layout::new::<[u8; 8]>()) };
+    }
+}
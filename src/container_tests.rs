@@ -2,6 +2,14 @@ use std::any::Any;
 use std::marker::PhantomPinned;
 use std::pin::pin;
 
+#[cfg(feature = "allocator-api2")]
+use allocator_api2::alloc::Global;
+#[cfg(feature = "arrayvec")]
+use arrayvec::ArrayVec;
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+#[cfg(feature = "bytes")]
+use bytes::BytesMut;
 use rstest::rstest;
 #[cfg(feature = "smallvec")]
 use smallvec::SmallVec;
@@ -25,6 +33,9 @@ where
 #[case(&mut MaybeUninit::<[u8; 12]>::uninit())]
 #[case(&mut [MaybeUninit::new(0u8); 12])]
 #[case(&mut [MaybeUninit::new(0u8); 12] as &mut [MaybeUninit<u8>])]
+#[case(&mut StackBuf::<12, u8>::new())]
+#[case(&mut newheap_fixed(12))]
+#[cfg_attr(feature = "arrayvec", case(&mut ArrayVec::<u8, 12>::new()) )]
 fn fix_sized_containers<C>(#[case] c: &mut C)
 where
     C: ?Sized,
@@ -43,9 +54,13 @@ where
 
 #[rstest]
 #[case(Boxed)]
+#[case(TryBoxed)]
+#[cfg_attr(feature = "allocator-api2", case(BoxedIn(Global)))]
 #[case(&mut Vec::<MaybeUninit<u8>>::new())]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 0]>::new()) )]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 12]>::new()) )]
+#[cfg_attr(feature = "bumpalo", case(&Bump::new()) )]
+#[cfg_attr(feature = "bytes", case(&mut BytesMut::new()) )]
 fn allocated_containers(#[case] c: impl DebugEmplace) {
     let inp = randarr::<16>();
     let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
@@ -55,11 +70,16 @@ fn allocated_containers(#[case] c: impl DebugEmplace) {
 
 #[rstest]
 #[case(Boxed)]
+#[case(TryBoxed)]
+#[cfg_attr(feature = "allocator-api2", case(BoxedIn(Global)))]
 #[case(&mut [MaybeUninit::new(0u8); 64])]
 #[case(&mut [MaybeUninit::uninit(); 64] as &mut [MaybeUninit<u8>])]
 #[case(&mut Vec::<MaybeUninit<u8>>::new())]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 0]>::new()) )]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 12]>::new()) )]
+#[cfg_attr(feature = "bumpalo", case(&Bump::new()) )]
+#[cfg_attr(feature = "bytes", case(&mut BytesMut::new()) )]
+#[case(Chain(&mut newstk::<16>(), &mut Vec::<MaybeUninit<u8>>::new()))]
 fn init_object_of_random_layout(#[case] c: impl DebugEmplace) {
     macro_rules! select_layout {
         ($rand:ident, $($align:literal),+) => {$(
@@ -83,11 +103,19 @@ fn init_object_of_random_layout(#[case] c: impl DebugEmplace) {
 
 #[rstest]
 #[case(Boxed)]
+#[case(TryBoxed)]
+#[cfg_attr(feature = "allocator-api2", case(BoxedIn(Global)))]
 #[case(&mut Vec::<MaybeUninit<u8>>::new())]
 #[case(&mut [] as &mut [MaybeUninit<u8>])]
 #[case(&mut [] as &mut [MaybeUninit<u8>; 0])]
+#[case(&mut StackBuf::<0>::new())]
+#[case(AlignedBuf::<u64>(&mut []))]
+#[case(&mut newheap_fixed(0))]
+#[cfg_attr(feature = "arrayvec", case(&mut ArrayVec::<u8, 0>::new()) )]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 0]>::new()) )]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 12]>::new()) )]
+#[cfg_attr(feature = "bumpalo", case(&Bump::new()) )]
+#[cfg_attr(feature = "bytes", case(&mut BytesMut::new()) )]
 fn never_fail_on_zst(#[case] c: impl DebugEmplace) {
     #[repr(align(4096))]
     struct Zst;
@@ -101,9 +129,13 @@ fn never_fail_on_zst(#[case] c: impl DebugEmplace) {
 #[rstest]
 #[case(&mut newstk::<24>())]
 #[case(&mut newstk::<24>() as &mut [MaybeUninit<u8>])]
+#[case(&mut StackBuf::<24>::new())]
+#[cfg_attr(feature = "arrayvec", case(&mut ArrayVec::<u8, 24>::new()) )]
 #[case(&mut Vec::<MaybeUninit<u8>>::new())]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 0]>::new()) )]
 #[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 12]>::new()) )]
+#[cfg_attr(feature = "bumpalo", case(&Bump::new()) )]
+#[cfg_attr(feature = "bytes", case(&mut BytesMut::new()) )]
 fn drop_buffered<'a>(#[case] c: impl 'a + DebugEmplace<Ptr = Buffered<'a, dyn Any>>) {
     let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
     let out = c.emplace(init).unwrap();
@@ -138,6 +170,408 @@ fn sync_buffered() {
     ensure_sync(val);
 }
 
+#[test]
+fn poll_buffered_across_threads() {
+    let mut stack = newstk::<64>();
+    let inp = randstr(8..64);
+    let init = from_closure(|slot| {
+        slot.write(async { inp.clone() })
+            as &mut Opaque<dyn Send + std::future::Future<Output = String>>
+    });
+    let fut = init.init(&mut stack);
+    let out = std::thread::scope(|s| s.spawn(|| pollster::block_on(fut)).join().unwrap());
+    assert_eq!(out, inp);
+}
+
+#[test]
+fn leak_buffered() {
+    let mut stack = newstk::<16>();
+    let init = from_closure(|slot| slot.write((DropCounter, 1usize)));
+    let val = Buffered::leak(init.init(&mut stack));
+    val.1 += 1;
+    assert_eq!(val.1, 2);
+    assert_eq!(DropCounter::count(), 0);
+}
+
+#[test]
+fn move_buffered_to_box() {
+    let inp = randstr(8..64);
+    let out = {
+        let mut stack = newstk::<32>();
+        let init = from_closure(|slot| slot.write((DropCounter, inp.clone())) as &mut OpqAny);
+        init.init(&mut stack).into_boxed()
+    };
+    assert_eq!(DropCounter::count(), 0);
+    assert_eq!(
+        out.downcast_ref::<(DropCounter, String)>().map(|v| &v.1),
+        Some(&inp)
+    );
+    drop(out);
+    assert_eq!(DropCounter::count(), 1);
+
+    let mut stack = newstk::<0>();
+    let init = from_closure(|slot| slot.write(()) as &mut OpqAny);
+    let out = init.init(&mut stack).into_boxed();
+    assert!(out.is::<()>());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn downcast_buffered_error() {
+    use std::error::Error;
+    use std::fmt;
+
+    #[derive(Debug, PartialEq)]
+    struct MyError(String);
+    impl fmt::Display for MyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+    impl Error for MyError {}
+
+    let mut stack = newstk::<32>();
+    let inp = randstr(8..16);
+
+    let init = from_closure(|slot| slot.write(MyError(inp.clone())) as &mut Opaque<dyn Error>);
+    let val = init.init(&mut stack);
+    let val = val.downcast::<std::fmt::Error>().unwrap_err();
+    assert_eq!(*val.downcast::<MyError>().unwrap(), MyError(inp.clone()));
+
+    let init =
+        from_closure(|slot| slot.write(MyError(inp.clone())) as &mut Opaque<dyn Error + Send>);
+    let val = init.init(&mut stack);
+    assert_eq!(*val.downcast::<MyError>().unwrap(), MyError(inp.clone()));
+
+    let init = from_closure(|slot| {
+        slot.write(MyError(inp.clone())) as &mut Opaque<dyn Error + Send + Sync>
+    });
+    let val = init.init(&mut stack);
+    assert_eq!(*val.downcast::<MyError>().unwrap(), MyError(inp));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn forward_buffered_error() {
+    use std::error::Error;
+
+    fn parse<'a>(stack: &'a mut [MaybeUninit<u8>], s: &str) -> Result<i32, Box<dyn 'a + Error>> {
+        let init = match s.parse::<i32>() {
+            Ok(n) => return Ok(n),
+            Err(e) => from_closure(|slot| slot.write(e) as &mut Opaque<dyn Error>),
+        };
+        Err(init.init(stack))?
+    }
+
+    let mut stack = newstk::<32>();
+    assert_eq!(parse(&mut stack, "7").unwrap(), 7);
+    let err = parse(&mut stack, "x").unwrap_err();
+    assert_eq!(err.to_string(), "invalid digit found in string");
+    assert!(err.source().is_none());
+}
+
+#[cfg(feature = "futures")]
+#[pollster::test]
+async fn poll_buffered_stream() {
+    use std::task::{Context, Poll};
+
+    use futures_core::stream::{FusedStream, Stream};
+
+    struct Countdown(usize);
+    impl Stream for Countdown {
+        type Item = usize;
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<usize>> {
+            if self.0 == 0 {
+                return Poll::Ready(None);
+            }
+            self.0 -= 1;
+            Poll::Ready(Some(self.0))
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.0, Some(self.0))
+        }
+    }
+    impl FusedStream for Countdown {
+        fn is_terminated(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    let mut stack = newstk::<16>();
+    let init =
+        from_closure(|slot| slot.write(Countdown(3)) as &mut Opaque<dyn FusedStream<Item = usize>>);
+    let mut stream = pin!(init.init(&mut stack));
+    assert_eq!(stream.size_hint(), (3, Some(3)));
+
+    let mut items = Vec::new();
+    while let Some(i) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        items.push(i);
+    }
+    assert_eq!(items, [2, 1, 0]);
+    assert!(stream.is_terminated());
+}
+
+#[test]
+fn display_buffered() {
+    let mut stack = newstk::<32>();
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut Opaque<dyn std::fmt::Display>);
+    let val = init.init(&mut stack);
+    assert_eq!(format!("<{}>", val), format!("<{}>", inp));
+}
+
+#[test]
+fn call_buffered() {
+    let mut stack = newstk::<32>();
+    let base = fastrand::usize(..64);
+
+    let init =
+        from_closure(|slot| slot.write(move |n| base + n) as &mut Opaque<dyn Fn(usize) -> usize>);
+    let add = init.init(&mut stack);
+    assert_eq!(add(1), base + 1);
+    drop(add);
+
+    let mut count = 0;
+    let init = from_closure(|slot| slot.write(|| count += 1) as &mut Opaque<dyn FnMut()>);
+    let mut incr = init.init(&mut stack);
+    incr();
+    incr();
+    drop(incr);
+    assert_eq!(count, 2);
+
+    let inp = randstr(8..16);
+    let expected = inp.clone();
+    let init = from_closure(|slot| slot.write(move || inp));
+    let take = init.init(&mut stack).into_inner();
+    assert_eq!(take(), expected);
+}
+
+#[pollster::test]
+async fn pin_buffered_in_place() {
+    let mut stack = newstk::<64>();
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(Box::pin(async { inp.clone() })));
+    let mut fut = init.init(&mut stack);
+    assert_eq!(fut.as_pin_mut().await, inp);
+    drop(fut);
+
+    let init = from_closure(|slot| slot.write(PhantomPinned));
+    let mut val = init.init(&mut stack);
+    let _: Pin<&mut PhantomPinned> = unsafe { val.as_pin_mut_unchecked() };
+}
+
+#[test]
+fn shorten_buffered_lifetime() {
+    fn shorten<'s, 'l: 's, T: ?Sized>(val: Buffered<'l, T>) -> Buffered<'s, T> {
+        val
+    }
+    fn tie<'a>(val: Buffered<'a, dyn Any>, _: &'a ()) -> Buffered<'a, dyn Any> {
+        val
+    }
+
+    let mut stack = newstk::<16>();
+    let init = from_closure(|slot| slot.write(123usize) as &mut OpqAny);
+    let val = init.init(&mut stack);
+    let short = ();
+    let val = tie(shorten(val), &short);
+    assert_eq!(val.downcast_ref::<usize>(), Some(&123));
+}
+
+#[test]
+fn map_buffered_unsize() {
+    let mut stack = newstk::<32>();
+    let inp = randarr::<8>();
+    let init = from_closure(|slot| slot.write(inp));
+    let val = init.init(&mut stack).map_unsize(|v| v as &mut [u8]);
+    assert_eq!(&*val, &inp);
+    let val = val.map_unsize(|v| v);
+    drop(val);
+
+    let init = from_closure(|slot| slot.write((DropCounter, 1)));
+    let val = init.init(&mut stack).map_unsize(|v| v as &mut dyn Any);
+    assert!(val.is::<(DropCounter, i32)>());
+    drop(val);
+    assert_eq!(DropCounter::count(), 1);
+}
+
+#[test]
+fn reerase_buffered() {
+    use std::fmt::Display;
+
+    let mut stk = newstk::<32>();
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let val = init.init(&mut stk);
+    let val = val.downcast::<String>().unwrap();
+    #[cfg(not(dynify_unsize))]
+    let val: Buffered<dyn Display> = val.map_unsize(|v| v as &mut dyn Display);
+    #[cfg(dynify_unsize)]
+    let val: Buffered<dyn Display> = val.unsize();
+    assert_eq!(val.to_string(), inp);
+}
+
+#[test]
+#[should_panic = "unsized pointer must point to the same object"]
+fn map_buffered_to_other_object() {
+    let mut stack = newstk::<32>();
+    let init = from_closure(|slot| slot.write((0usize, 1usize)));
+    let _ = init
+        .init(&mut stack)
+        .map_unsize(|v| &mut v.1 as &mut dyn Any);
+}
+
+#[test]
+fn clone_buffered_into() {
+    let mut stk1 = newstk::<32>();
+    let mut stk2 = newstk::<32>();
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()));
+    let val = init.init(&mut stk1);
+
+    let cloned = Buffered::clone_into(&val, &mut stk2).unwrap();
+    assert_eq!(*cloned, inp);
+    drop(cloned);
+    let mut stk3 = newstk::<8>();
+    assert!(Buffered::clone_into(&val, &mut stk3).is_err());
+    assert_eq!(*val, inp);
+}
+
+#[test]
+fn replace_buffered() {
+    let mut stk = newstk::<32>();
+    let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+    let val = init.init(&mut stk);
+
+    let inp = randarr::<16>();
+    let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+    let (val, init) = Buffered::replace_with(val, init).unwrap_err();
+    assert!(val.is::<DropCounter>());
+    assert_eq!(DropCounter::count(), 0);
+    drop((val, init));
+
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let val = init.init(&mut stk);
+    let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+    let val = Buffered::replace_with(val, init).unwrap_or_else(|_| unreachable!());
+    assert!(val.is::<DropCounter>());
+    drop(val);
+    assert_eq!(DropCounter::count(), 2);
+}
+
+#[test]
+fn peek_buffered_any() {
+    let mut stk = newstk::<32>();
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut Opaque<dyn Any + Send>);
+    let mut val = init.init(&mut stk);
+    assert!(val.downcast_ref::<i32>().is_none());
+    val.downcast_mut::<String>().unwrap().push('!');
+
+    let out = val.downcast::<String>().unwrap();
+    assert_eq!(*out, format!("{inp}!"));
+}
+
+#[test]
+fn borrow_buffered() {
+    use std::borrow::{Borrow, BorrowMut};
+
+    fn push_as_mut(v: &mut impl AsMut<String>, s: &str) {
+        v.as_mut().push_str(s);
+    }
+    fn push_borrow_mut(v: &mut impl BorrowMut<String>, s: &str) {
+        v.borrow_mut().push_str(s);
+    }
+
+    let mut stk = newstk::<32>();
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()));
+    let mut val = init.init(&mut stk);
+    push_as_mut(&mut val, "!");
+    push_borrow_mut(&mut val, "?");
+    let expected = format!("{inp}!?");
+    assert_eq!(AsRef::<String>::as_ref(&val), &expected);
+    assert_eq!(Borrow::<String>::borrow(&val), &expected);
+}
+
+#[test]
+fn report_buffered_layout() {
+    let mut stk = newstk::<64>();
+    let init = from_closure(|slot| slot.write(randarr::<33>()) as &mut OpqAny);
+    let val = init.init(&mut stk);
+    assert_eq!(Buffered::layout(&val), Layout::new::<[u8; 33]>());
+    drop(val);
+
+    let init = from_closure(|slot| slot.write(randstr(8..16)));
+    let val = init.init(&mut stk);
+    assert_eq!(Buffered::layout(&val), Layout::new::<String>());
+}
+
+#[test]
+fn access_buffered_pointer() {
+    let mut stk = newstk::<32>();
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let mut val = init.init(&mut stk);
+
+    let ptr = Buffered::as_ptr(&val);
+    assert!(std::ptr::eq(ptr, &*val));
+    #[cfg(dynify_ptr_metadata)]
+    assert_eq!(Buffered::metadata(&val), std::ptr::metadata(ptr));
+    let ptr = Buffered::as_non_null(&mut val);
+    let out = unsafe { ptr.as_ref() };
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_buffered() {
+    use serde_test::{assert_ser_tokens, Token};
+
+    let mut stk = newstk::<32>();
+    let init = from_closure(|slot| slot.write(String::from("dynify")));
+    let val = init.init(&mut stk);
+    assert_ser_tokens(&val, &[Token::Str("dynify")]);
+    drop(val);
+
+    let init = from_closure(|slot| slot.write([1u8, 2, 3]));
+    let val = init.init(&mut stk).map_unsize(|v| v as &mut [u8]);
+    let tokens = [
+        Token::Seq { len: Some(3) },
+        Token::U8(1),
+        Token::U8(2),
+        Token::U8(3),
+        Token::SeqEnd,
+    ];
+    assert_ser_tokens(&val, &tokens);
+}
+
+#[cfg(not(all(feature = "debug-poison", debug_assertions)))]
+#[test]
+fn no_poison_flag_when_disabled() {
+    assert_eq!(
+        std::mem::size_of::<Buffered<dyn Any>>(),
+        std::mem::size_of::<&dyn Any>()
+    );
+}
+
+#[cfg(all(feature = "debug-poison", debug_assertions))]
+#[test]
+#[should_panic = "object is poisoned by a panic during polling"]
+fn poison_buffered_on_panic() {
+    use std::future::Future;
+    use std::panic::AssertUnwindSafe;
+
+    let mut stk = newstk::<16>();
+    let init = from_closure(|slot| {
+        slot.write(async { panic!("polled") }) as &mut Opaque<dyn Future<Output = ()>>
+    });
+    let mut fut = pin!(init.init(&mut stk));
+    let res = std::panic::catch_unwind(AssertUnwindSafe(|| pollster::block_on(fut.as_mut())));
+    assert!(res.is_err());
+    pollster::block_on(fut);
+}
+
 #[test]
 fn project_buffered() {
     let mut stack = newstk::<16>();
@@ -184,6 +618,35 @@ fn default_pin_emplace() {
     assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&inp));
 }
 
+#[cfg(feature = "allocator-api2")]
+#[test]
+fn default_pin_emplace_in() {
+    let inp = randarr::<16>();
+    let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+    let out = BoxedIn(Global).pin_emplace(init).unwrap();
+    assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&inp));
+}
+
+#[cfg(feature = "allocator-api2")]
+#[test]
+fn boxed_in_alloc_error() {
+    use allocator_api2::alloc::{AllocError, Allocator};
+
+    struct NoMemory;
+    unsafe impl Allocator for NoMemory {
+        fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+        unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {
+            unreachable!()
+        }
+    }
+
+    let init = from_closure(|slot| slot.write(123) as &mut OpqAny);
+    let (_, err) = init.try_init(BoxedIn(NoMemory)).unwrap_err();
+    assert_eq!(err, AllocError);
+}
+
 #[test]
 #[should_panic = "just panic"]
 fn clean_up_boxed_zst_on_panic() {
@@ -196,6 +659,60 @@ fn clean_up_boxed_on_panic() {
     let _ = from_closure::<usize, usize, _>(|_| panic!("just panic")).boxed();
 }
 
+#[rstest]
+#[case(&mut newstk::<32>())]
+#[case(&mut StackBuf::<32>::new())]
+#[case(&mut Vec::<MaybeUninit<u8>>::new())]
+#[cfg_attr(feature = "smallvec", case(&mut SmallVec::<[MaybeUninit<u8>; 8]>::new()))]
+fn reuse_buffer_after_panic<C>(#[case] container: &mut C)
+where
+    for<'a> &'a mut C: Emplace<dyn Any>,
+{
+    panic_in(&mut *container);
+    init_after_panic(&mut *container);
+}
+
+#[test]
+fn reuse_other_containers_after_panic() {
+    let ring = crate::RingBuffer::<64>::new();
+    panic_in(&ring);
+    init_after_panic(&ring);
+
+    let pool = crate::Pool::new();
+    panic_in(&pool);
+    init_after_panic(&pool);
+
+    let mut pinned = pin!(crate::PinBuffer::<32>::new());
+    panic_in(pinned.as_mut());
+    init_after_panic(pinned.as_mut());
+
+    let mut once = crate::OnceSlot::<dyn Any, 32>::new();
+    panic_in(&mut once);
+    init_after_panic(&mut once);
+
+    #[cfg(feature = "smallvec")]
+    {
+        let mut heap = SmallVec::<[MaybeUninit<u8>; 8]>::new();
+        panic_in(Spillable(&mut heap));
+        init_after_panic(Spillable(&mut heap));
+    }
+}
+
+fn panic_in(container: impl Emplace<dyn Any>) {
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let init = from_closure::<String, dyn Any, _>(|_| panic!("just panic"));
+        let _ = init.try_init(container);
+    }));
+    assert!(res.is_err());
+}
+
+fn init_after_panic(container: impl Emplace<dyn Any>) {
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let out = init.try_init(container).ok().unwrap();
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+}
+
 #[rstest]
 #[case(fastrand::i32(..))]
 #[case(randstr(16..32))]
@@ -224,3 +741,372 @@ where
     let val = val.downcast().ok().map(Buffered::into_inner);
     assert_eq!(val, Some(data.clone()));
 }
+
+#[test]
+fn bounded_rejects_large_object() {
+    let mut heap = Vec::<MaybeUninit<u8>>::new();
+    let mut stk = newstk::<24>();
+
+    let init = from_closure(|slot| slot.write(randarr::<16>()));
+    assert!(init.try_init(Bounded::new(Boxed, 16)).is_ok());
+    let init = from_closure(|slot| slot.write(randarr::<24>()));
+    let (init, _) = init.try_init(Bounded::new(Boxed, 16)).unwrap_err();
+    let (init, _) = init.try_init(Bounded::new(&mut heap, 16)).unwrap_err();
+    let (_, _) = init.try_init(Bounded::new(&mut stk, 16)).unwrap_err();
+    assert_eq!(heap.capacity(), 0);
+}
+
+#[test]
+fn out_of_capacity_reports_layout() {
+    let mut stk = StackBuf::<16, u64>::new();
+    let init = from_closure(|slot| slot.write(randarr::<24>()));
+    let (init, err) = init.try_init(&mut stk).unwrap_err();
+    assert_eq!(err, OutOfCapacity::new(Layout::new::<[u8; 24]>(), 16));
+    let (_, err) = init.try_init(Bounded::new(Boxed, 8)).unwrap_err();
+    assert_eq!(err.available(), 8);
+    assert_eq!(
+        err.to_string(),
+        "out of capacity: required 24 bytes aligned to 1, but only 8 bytes available",
+    );
+}
+
+#[test]
+fn vec_container_grows_exactly() {
+    let mut heap = Vec::<MaybeUninit<u8>>::new();
+    for _ in 0..4 {
+        let inp = [fastrand::u64(..); 3];
+        let init = from_closure(|slot| slot.write(inp));
+        let out = init.init(VecContainer::with_exact_capacity(&mut heap));
+        assert_eq!(*out, inp);
+        drop(out);
+        // The buffer is reused across constructions.
+        assert_eq!(heap.capacity(), 24 + 8 - 1);
+    }
+
+    let init = from_closure(|slot| slot.write(randarr::<48>()));
+    let _ = init.init(VecContainer::with_exact_capacity(&mut heap));
+    assert_eq!(heap.capacity(), 48);
+}
+
+#[test]
+fn vec_reuses_initialized_capacity() {
+    let mut heap = newheap(32);
+    let init = from_closure(|slot| slot.write(randarr::<32>()));
+    let _ = init.init(VecContainer::new(&mut heap));
+    assert_eq!(heap.capacity(), 32);
+
+    let init = from_closure(|slot| slot.write(randarr::<40>()));
+    let _ = init.init(&mut heap);
+    assert!(heap.capacity() >= 40);
+}
+
+#[test]
+fn reset_vec() {
+    let mut heap = newheap(8);
+    let init = from_closure(|slot| slot.write(randarr::<64>()));
+    drop(init.init(&mut heap));
+    assert!(heap.capacity() >= 64);
+    heap.reset();
+    assert_eq!((heap.len(), heap.capacity()), (0, 0));
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn reset_small_vec() {
+    let mut heap = SmallVec::<[MaybeUninit<u8>; 16]>::new();
+    let init = from_closure(|slot| slot.write(randarr::<64>()));
+    drop(init.init(&mut heap));
+    assert!(heap.spilled());
+    heap.reset();
+    assert!(!heap.spilled());
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn report_small_vec_placement() {
+    let mut heap = SmallVec::<[MaybeUninit<u8>; 16]>::new();
+    let inp = randarr::<8>();
+    let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+    let out = init.init(Spillable(&mut heap));
+    assert!(out.is_inline());
+    assert_eq!(out.downcast_ref::<[u8; 8]>(), Some(&inp));
+    drop(out);
+    assert!(!heap.spilled());
+
+    let init = from_closure(|slot| slot.write(randarr::<64>()) as &mut OpqAny);
+    let out = init.init(Spillable(&mut heap));
+    assert!(!out.is_inline());
+    drop(out.into_buffered());
+    assert!(heap.spilled());
+
+    let init = from_closure(|slot| slot.write(()) as &mut OpqAny);
+    assert!(init.init(Spillable(&mut heap)).is_inline());
+}
+
+#[test]
+fn chain_falls_back() {
+    let mut stk1 = newstk::<8>();
+    let mut stk2 = newstk::<16>();
+    let inp = randarr::<12>();
+
+    let init = from_closure(|slot| slot.write(inp));
+    let out = init.init(Chain(&mut stk1, &mut stk2));
+    assert_eq!(*out, inp);
+    drop(out);
+
+    let init = from_closure(|slot| slot.write(randarr::<24>()));
+    assert!(init.try_init(Chain(&mut stk1, &mut stk2)).is_err());
+}
+
+#[test]
+fn hybrid_never_fails() {
+    let mut stk = newstk::<16>();
+    let mut heap = Vec::new();
+
+    let inp = randarr::<8>();
+    let init = from_closure(|slot| slot.write(inp));
+    let out = init.init(hybrid(&mut stk, &mut heap));
+    assert_eq!(*out, inp);
+    drop(out);
+    assert_eq!(heap.capacity(), 0);
+
+    let inp = randarr::<24>();
+    let init = from_closure(|slot| slot.write(inp));
+    let out = init.init(hybrid(&mut stk, &mut heap));
+    assert_eq!(*out, inp);
+    drop(out);
+    assert!(heap.capacity() >= 24);
+}
+
+#[test]
+fn init_in_uninit() {
+    let mut field = MaybeUninit::<(DropCounter, String)>::uninit();
+    let inp = randstr(8..64);
+    let init = from_closure(|slot| slot.write((DropCounter, inp.clone())));
+    let out = init.init(Uninit::from(&mut field));
+    assert_eq!((*out).1, inp);
+    drop(out);
+    assert_eq!(DropCounter::count(), 1);
+}
+
+#[test]
+fn stack_buf_fits_aligned_object() {
+    let mut stack = StackBuf::<16, u128>::new();
+    let inp = fastrand::u128(..);
+    let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+    let out = init.init(&mut stack);
+    assert_eq!(out.downcast_ref::<u128>(), Some(&inp));
+    drop(out);
+
+    let init = from_closure(|slot| slot.write(randarr::<17>()) as &mut OpqAny);
+    assert!(init.try_init(&mut stack).is_err());
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn bump_holds_multiple_objects() {
+    let bump = Bump::new();
+    let inp1 = randstr(8..64);
+    let inp2 = randstr(8..64);
+    let init1 = from_closure(|slot| slot.write(inp1.clone()) as &mut OpqAny);
+    let init2 = from_closure(|slot| slot.write(inp2.clone()) as &mut OpqAny);
+    let out1: Buffered<dyn Any> = init1.init(&bump);
+    let out2: Buffered<dyn Any> = init2.init(&bump);
+    assert_eq!(out1.downcast_ref::<String>(), Some(&inp1));
+    assert_eq!(out2.downcast_ref::<String>(), Some(&inp2));
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes_mut_keeps_contents() {
+    let mut bytes = BytesMut::from(&randarr::<8>()[..]);
+    let contents = bytes.clone();
+
+    let inp = randarr::<64>();
+    let init = from_closure(|slot| slot.write(inp));
+    let out = init.init(&mut bytes);
+    assert_eq!(*out, inp);
+    drop(out);
+    assert_eq!(bytes, contents);
+    assert!(bytes.capacity() >= 8 + 64);
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn array_vec_keeps_elements() {
+    let mut vec = ArrayVec::<u8, 16>::new();
+    vec.extend(randarr::<8>());
+    let elems = vec.clone();
+
+    let init = from_closure(|slot| slot.write(0xFFFF_FFFF_u32));
+    let out = init.init(&mut vec);
+    assert_eq!(*out, 0xFFFF_FFFF);
+    drop(out);
+    assert_eq!(vec, elems);
+
+    let init = from_closure(|slot| slot.write(randarr::<12>()));
+    assert!(init.try_init(&mut vec).is_err());
+}
+
+#[test]
+fn try_boxed_reports_alloc_error() {
+    struct Huge;
+    unsafe impl PinConstruct for Huge {
+        type Object = u8;
+        fn layout(&self) -> Layout {
+            Layout::from_size_align(isize::MAX as usize / 2, 1).unwrap()
+        }
+        unsafe fn construct(self, _: Slot) -> NonNull<Self::Object> {
+            unreachable!()
+        }
+    }
+    unsafe impl Construct for Huge {}
+
+    let (_, err) = Huge.try_boxed().unwrap_err();
+    assert_eq!(err.layout().size(), isize::MAX as usize / 2);
+}
+
+#[test]
+fn spare_capacity_keeps_elements() {
+    let mut items = vec![randstr(8..16), randstr(8..16)];
+    items.reserve(4);
+    let expected = items.clone();
+
+    let inp = randarr::<16>();
+    let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+    let out = init.init(SpareCapacity(&mut items));
+    assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&inp));
+    drop(out);
+    assert_eq!(items, expected);
+
+    items.shrink_to_fit();
+    let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+    assert!(init.try_init(SpareCapacity(&mut items)).is_err());
+}
+
+#[test]
+fn capacity_hint_predicts_emplace() {
+    let mut stk = StackBuf::<16>::new();
+    let container = &mut stk;
+    assert!(container.can_fit(Layout::new::<[u64; 2]>()));
+    assert!(!container.can_fit(Layout::new::<[u8; 17]>()));
+
+    // The result depends on the alignment of the buffer.
+    let mut stk = newstk::<20>();
+    let buf = &mut stk[1..];
+    let fits = buf.can_fit(Layout::new::<u128>());
+    let init = from_closure(|slot| slot.write(fastrand::u128(..)));
+    assert_eq!(init.try_init(buf).is_ok(), fits);
+
+    let heap = Bounded::new(Boxed, 8);
+    assert!(heap.can_fit(Layout::new::<u64>()));
+    assert!(!heap.can_fit(Layout::new::<u128>()));
+    let chain = Chain(&mut stk[..8], heap);
+    assert!(chain.can_fit(Layout::new::<[u8; 8]>()));
+    assert!(!chain.can_fit(Layout::new::<[u8; 9]>()));
+}
+
+#[test]
+fn raw_container_checks_bounds() {
+    let mut stk = StackBuf::<24, u64>::new();
+    let ptr = NonNull::from(&mut stk).cast::<u8>();
+    let layout = Layout::from_size_align(24, 8).unwrap();
+
+    let inp = randarr::<24>();
+    let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+    let out = init.init(unsafe { RawContainer::new(ptr, layout) });
+    assert_eq!(out.downcast_ref::<[u8; 24]>(), Some(&inp));
+    drop(out);
+
+    let container = unsafe { RawContainer::new(ptr.add(1), Layout::new::<[u8; 23]>()) };
+    assert!(!container.can_fit(Layout::new::<[u64; 3]>()));
+    let init = from_closure(|slot| slot.write([0u64; 3]) as &mut OpqAny);
+    let (_, err) = init.try_init(container).unwrap_err();
+    assert_eq!(err.available(), 23);
+}
+
+#[test]
+fn emplace_in_custom_buffer() {
+    struct Custom(Vec<MaybeUninit<u8>>);
+    impl AsRef<[MaybeUninit<u8>]> for Custom {
+        fn as_ref(&self) -> &[MaybeUninit<u8>] {
+            &self.0
+        }
+    }
+    impl AsMut<[MaybeUninit<u8>]> for Custom {
+        fn as_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+            &mut self.0
+        }
+    }
+
+    let mut buf = Custom(vec![MaybeUninit::uninit(); 32]);
+    assert!(AsBuffer(&mut buf).can_fit(Layout::new::<[u8; 32]>()));
+    assert!(!AsBuffer(&mut buf).can_fit(Layout::new::<[u8; 33]>()));
+
+    let inp = randstr(8..64);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let out = init.init(AsBuffer(&mut buf));
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+    drop(out);
+
+    let init = from_closure(|slot| slot.write(randarr::<33>()) as &mut OpqAny);
+    assert!(init.try_init(AsBuffer(&mut buf)).is_err());
+}
+
+#[test]
+fn reserve_then_commit() {
+    let mut stk = newstk::<16>();
+    let mut heap = Vec::<MaybeUninit<u8>>::new();
+    let inp = randstr(8..64);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let layout = init.layout();
+
+    let reserved = match (&mut stk[..]).reserve_slot(layout) {
+        Ok(reserved) => reserved,
+        Err((_, err)) => {
+            assert_eq!(err.available(), 16);
+            (&mut heap).reserve_slot(layout).unwrap()
+        },
+    };
+    assert_eq!(reserved.layout(), layout);
+
+    let init = from_closure(|slot| slot.write(0u8) as &mut OpqAny);
+    let (reserved, init) = reserved.commit(init).unwrap_err();
+    drop(init);
+    let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+    let out = reserved.commit(init).ok().unwrap();
+    assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+}
+
+#[test]
+fn release_unused_reservation() {
+    let layout = Layout::new::<[u8; 64]>();
+    let reserved = TryReserve::<dyn Any>::reserve_slot(TryBoxed, layout).unwrap();
+    drop(reserved);
+
+    let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+    let reserved = TryBoxed.reserve_slot(init.layout()).unwrap();
+    drop(reserved.commit(init).ok().unwrap());
+    assert_eq!(DropCounter::count(), 1);
+}
+
+#[test]
+fn init_in_aligned_buf() {
+    let mut stk = MaybeUninit::<[u16; 6]>::uninit();
+    let init = from_closure(|slot| slot.write(randarr::<12>()) as &mut OpqAny);
+    assert!(AlignedBuf::from(&mut stk).can_fit(init.layout()));
+    let out = init.init(AlignedBuf::from(&mut stk));
+    assert!(out.is::<[u8; 12]>());
+    drop(out);
+
+    let mut stk = [MaybeUninit::<u32>::uninit(); 3];
+    let inp = randstr(8..16);
+    let init = from_closure(|slot| slot.write(inp.clone()));
+    let (init, _) = init.try_init(AlignedBuf::from(&mut stk)).unwrap_err();
+    let mut stk = [MaybeUninit::<u64>::uninit(); 3];
+    let reserved = AlignedBuf::from(&mut stk)
+        .reserve_slot(init.layout())
+        .ok()
+        .unwrap();
+    assert_eq!(*reserved.commit(init).ok().unwrap(), inp);
+}
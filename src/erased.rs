@@ -0,0 +1,449 @@
+use core::alloc::Layout;
+use core::fmt;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+use crate::closure::from_closure;
+use crate::constructor::{Construct, Dynify, Opaque, PinConstruct, Slot};
+use crate::container::{CapacityHint, Emplace};
+use crate::inline::{Inline, Inlined};
+
+/// An object-safe variant of [`Emplace`].
+///
+/// [`Emplace`] consumes the container and takes a generic constructor, which
+/// makes it impossible to use as a trait object. This trait instead borrows the
+/// container and takes a type-erased [`DynConstructor`], so that containers can
+/// be chosen at runtime through `&mut dyn DynEmplace<T, Ptr = _, Err = _>`,
+/// which implements [`Emplace`] in turn.
+///
+/// It is implemented for `Option<C>` of any container `C`, which is taken out
+/// on the first construction. Subsequent constructions panic.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Bounded, Buffered, DynEmplace, Dynify, OutOfCapacity};
+/// # use std::any::Any;
+/// # use std::mem::MaybeUninit;
+/// type DynContainer<'a> =
+///     dyn 'a + DynEmplace<dyn Any, Ptr = Buffered<'a, dyn Any>, Err = OutOfCapacity>;
+///
+/// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+/// let mut heap = Vec::<MaybeUninit<u8>>::new();
+/// let mut stack = Some(&mut stack);
+/// let mut heap = Some(Bounded::new(&mut heap, 1024));
+///
+/// let size = 64;
+/// let container: &mut DynContainer = if size <= 16 { &mut stack } else { &mut heap };
+/// let val = from_closure(|slot| slot.write([0u8; 64]) as &mut _).init(container);
+/// assert_eq!(val.downcast_ref::<[u8; 64]>(), Some(&[0; 64]));
+/// ```
+///
+/// # Safety
+///
+/// See the safety notes of [`Emplace`].
+pub unsafe trait DynEmplace<T: ?Sized> {
+    type Ptr: Deref<Target = T>;
+    type Err;
+
+    /// Initializes the supplied constructor in this container.
+    ///
+    /// It works the same as [`Emplace::emplace`], except that the container
+    /// is borrowed rather than consumed.
+    fn dyn_emplace(&mut self, constructor: DynConstructor<'_, T>) -> Result<Self::Ptr, Self::Err>;
+}
+
+unsafe impl<T: ?Sized, C: Emplace<T>> DynEmplace<T> for Option<C> {
+    type Ptr = C::Ptr;
+    type Err = C::Err;
+
+    fn dyn_emplace(&mut self, constructor: DynConstructor<'_, T>) -> Result<Self::Ptr, Self::Err> {
+        self.take()
+            .expect("container has been consumed")
+            .emplace(constructor)
+    }
+}
+
+unsafe impl<T, P, E> Emplace<T> for &mut (dyn '_ + DynEmplace<T, Ptr = P, Err = E>)
+where
+    T: ?Sized,
+    P: Deref<Target = T>,
+{
+    type Ptr = P;
+    type Err = E;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        let mut constructor = Some(constructor);
+        self.dyn_emplace(DynConstructor::new(&mut constructor))
+    }
+}
+
+/// A type-erased constructor passed to [`DynEmplace`].
+///
+/// It borrows the actual constructor, so that functions can accept any
+/// constructor of `T` without being generic, e.g., across trait objects or
+/// plugin boundaries.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, DynConstructor, Dynify, Opaque};
+/// # use std::fmt::Display;
+/// fn describe(init: DynConstructor<'_, dyn Display>) -> String {
+///     init.boxed().to_string()
+/// }
+///
+/// let mut init = Some(from_closure(|slot| {
+///     slot.write(123) as &mut Opaque<dyn Display>
+/// }));
+/// assert_eq!(describe(DynConstructor::new(&mut init)), "123");
+/// assert!(init.is_none());
+/// ```
+pub struct DynConstructor<'a, T: ?Sized> {
+    inner: &'a mut dyn ErasedConstruct<T>,
+}
+impl<'a, T: ?Sized> DynConstructor<'a, T> {
+    /// Erases the type of the constructor in `constructor`.
+    ///
+    /// The constructor is taken out once the returned instance gets
+    /// constructed, leaving `None` behind.
+    ///
+    /// # Panic
+    ///
+    /// The returned instance panics on use if `constructor` is `None`.
+    pub fn new<C>(constructor: &'a mut Option<C>) -> Self
+    where
+        C: Construct<Object = T>,
+    {
+        Self { inner: constructor }
+    }
+}
+impl<T: ?Sized> fmt::Debug for DynConstructor<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynConstructor")
+            .field("layout", &self.inner.layout())
+            .finish()
+    }
+}
+
+unsafe impl<T: ?Sized> PinConstruct for DynConstructor<'_, T> {
+    type Object = T;
+    fn layout(&self) -> Layout {
+        self.inner.layout()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        self.inner.construct(slot)
+    }
+}
+unsafe impl<T: ?Sized> Construct for DynConstructor<'_, T> {}
+
+trait ErasedConstruct<T: ?Sized> {
+    fn layout(&self) -> Layout;
+    unsafe fn construct(&mut self, slot: Slot) -> NonNull<T>;
+}
+impl<C: Construct> ErasedConstruct<C::Object> for Option<C> {
+    fn layout(&self) -> Layout {
+        self.as_ref()
+            .expect("constructor has been consumed")
+            .layout()
+    }
+    unsafe fn construct(&mut self, slot: Slot) -> NonNull<C::Object> {
+        self.take()
+            .expect("constructor has been consumed")
+            .construct(slot)
+    }
+}
+
+/// An object-safe variant of [`Construct`].
+///
+/// It is implemented for all constructors, so that constructors of different
+/// types can be stored in collections as `Box<dyn DynConstruct<Object = T>>`,
+/// or inline as [`AnyConstructor`], both of which implement [`Construct`] in
+/// turn.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, DynConstruct, Dynify, Opaque};
+/// # use std::fmt::Display;
+/// type Factory = Box<dyn DynConstruct<Object = dyn Display>>;
+///
+/// let registry: Vec<Factory> = vec![
+///     Box::new(from_closure(|slot| {
+///         slot.write(123) as &mut Opaque<dyn Display>
+///     })),
+///     Box::new(from_closure(|slot| {
+///         slot.write("Hi!") as &mut Opaque<dyn Display>
+///     })),
+/// ];
+/// let output = registry
+///     .into_iter()
+///     .map(|f| f.boxed().to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(output, ["123", "Hi!"]);
+/// ```
+///
+/// # Safety
+///
+/// See the safety notes of [`Construct`].
+pub unsafe trait DynConstruct {
+    /// The type of objects to be constructed.
+    type Object: ?Sized;
+
+    /// Returns the layout of the object to be constructed.
+    ///
+    /// It works the same as [`PinConstruct::layout`], but is named differently
+    /// to avoid ambiguity, since all constructors implement this trait.
+    fn dyn_layout(&self) -> Layout;
+
+    /// Constructs the object in the specified address, moving this
+    /// constructor out of `self`.
+    ///
+    /// # Safety
+    ///
+    /// See the safety notes of [`PinConstruct::construct`]. Additionally,
+    /// `self` is moved out even if this function panics, so it may not be used
+    /// or dropped afterwards.
+    unsafe fn construct_in_place(&mut self, slot: Slot) -> NonNull<Self::Object>;
+}
+unsafe impl<C: Construct> DynConstruct for C {
+    type Object = C::Object;
+    fn dyn_layout(&self) -> Layout {
+        self.layout()
+    }
+    unsafe fn construct_in_place(&mut self, slot: Slot) -> NonNull<Self::Object> {
+        core::ptr::read(self).construct(slot)
+    }
+}
+
+/// A type-erased constructor stored inline.
+///
+/// It works the same as `Box<dyn DynConstruct<Object = T>>`, except that the
+/// constructor is stored in `N` bytes aligned to `A` without heap allocation.
+pub type AnyConstructor<'a, T, const N: usize, A = usize> =
+    Inlined<dyn 'a + DynConstruct<Object = T>, N, A>;
+impl<'a, T: ?Sized, const N: usize, A> AnyConstructor<'a, T, N, A> {
+    /// Erases the type of `constructor`, or returns it back if it does not fit
+    /// in `N` bytes aligned to `A`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_closure, AnyConstructor, Dynify, Opaque};
+    /// # use std::any::Any;
+    /// let init = from_closure(|slot| slot.write(String::from("Hi!")) as &mut Opaque<dyn Any>);
+    /// let init = AnyConstructor::<dyn Any, 16>::from_constructor(init)
+    ///     .ok()
+    ///     .unwrap();
+    /// assert_eq!(init.boxed().downcast_ref::<String>().unwrap(), "Hi!");
+    /// ```
+    pub fn from_constructor<C>(constructor: C) -> Result<Self, C>
+    where
+        C: 'a + Construct<Object = T>,
+    {
+        let container = Inline::<N, A>::new();
+        if !container.can_fit(Layout::new::<C>()) {
+            return Err(constructor);
+        }
+        let init = from_closure(|slot| slot.write(constructor) as &mut Opaque<_>);
+        Ok(init.init(container))
+    }
+}
+
+macro_rules! impl_erased_construct {
+    ($($bound:tt)*) => {
+        unsafe impl<T: ?Sized, const N: usize, A> PinConstruct
+            for Inlined<dyn '_ + DynConstruct<Object = T> $($bound)*, N, A>
+        {
+            type Object = T;
+            fn layout(&self) -> Layout {
+                (**self).dyn_layout()
+            }
+            unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+                let this = core::mem::ManuallyDrop::new(self);
+                (*this.as_ptr()).construct_in_place(slot)
+            }
+        }
+        unsafe impl<T: ?Sized, const N: usize, A> Construct
+            for Inlined<dyn '_ + DynConstruct<Object = T> $($bound)*, N, A>
+        {
+        }
+
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        unsafe impl<T: ?Sized> PinConstruct
+            for alloc::boxed::Box<dyn '_ + DynConstruct<Object = T> $($bound)*>
+        {
+            type Object = T;
+            fn layout(&self) -> Layout {
+                (**self).dyn_layout()
+            }
+            unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+                let ptr = alloc::boxed::Box::into_raw(self);
+                let layout = Layout::for_value(&*ptr);
+                // Release the memory without dropping the moved constructor.
+                let _dealloc = crate::utils::defer(|| {
+                    if layout.size() != 0 {
+                        alloc::alloc::dealloc(ptr.cast(), layout);
+                    }
+                });
+                (*ptr).construct_in_place(slot)
+            }
+        }
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        unsafe impl<T: ?Sized> Construct
+            for alloc::boxed::Box<dyn '_ + DynConstruct<Object = T> $($bound)*>
+        {
+        }
+    };
+}
+impl_erased_construct!();
+impl_erased_construct!(+ Send);
+impl_erased_construct!(+ Send + Sync);
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::mem::MaybeUninit;
+
+    use super::*;
+    use crate::utils::{newstk, randarr, randstr, DropCounter, OpqAny};
+    use crate::{from_closure, Buffered, Dynify, OutOfCapacity};
+
+    type DynContainer<'a> =
+        dyn 'a + DynEmplace<dyn Any, Ptr = Buffered<'a, dyn Any>, Err = OutOfCapacity>;
+
+    #[test]
+    fn choose_container_at_runtime() {
+        let mut stk1 = newstk::<32>();
+        let mut stk2 = newstk::<64>();
+        let mut containers: [Option<&mut [MaybeUninit<u8>]>; 2] =
+            [Some(&mut stk1), Some(&mut stk2)];
+        let [c1, c2] = &mut containers;
+
+        let inp = randstr(8..64);
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        let container: &mut DynContainer = if fastrand::bool() { c1 } else { c2 };
+        let out = init.init(container);
+        assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+    }
+
+    #[test]
+    fn reuse_constructor_on_failure() {
+        let mut stk = newstk::<8>();
+        let mut container = Some(&mut stk);
+        let container: &mut DynContainer = &mut container;
+
+        let inp = randarr::<16>();
+        let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+        let (init, _) = init.try_init(container).unwrap_err();
+        let out = init.boxed();
+        assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&inp));
+    }
+
+    #[pollster::test]
+    async fn dispatch_boxed_constructors() {
+        use std::future::Future;
+
+        use crate::utils::OpqStrFut;
+
+        type Factory<'a> = Box<dyn 'a + DynConstruct<Object = dyn Future<Output = String>> + Send>;
+
+        let inp1 = randstr(8..16);
+        let inp2 = randarr::<8>();
+        let expected = [inp1.clone(), format!("{inp2:?}")];
+        let registry: Vec<Factory> = vec![
+            Box::new(from_closure(|slot| {
+                slot.write(async move { inp1 }) as &mut OpqStrFut
+            })),
+            Box::new(from_closure(|slot| {
+                slot.write(async move { format!("{inp2:?}") }) as &mut OpqStrFut
+            })),
+        ];
+        let mut stk = newstk::<64>();
+        let mut out = Vec::new();
+        for factory in registry {
+            out.push(factory.init(&mut stk).await);
+        }
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn erase_borrowed_constructor() {
+        fn init_in(init: DynConstructor<'_, dyn Any>, stk: &mut [MaybeUninit<u8>]) -> String {
+            assert_eq!(init.layout(), Layout::new::<String>());
+            let out = init.init(stk);
+            out.downcast_ref::<String>().unwrap().clone()
+        }
+
+        let mut stk = newstk::<32>();
+        let inp = randstr(8..16);
+        let mut init = Some(from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny));
+        assert_eq!(init_in(DynConstructor::new(&mut init), &mut stk), inp);
+        assert!(init.is_none());
+    }
+
+    #[test]
+    #[should_panic = "constructor has been consumed"]
+    fn panic_on_consumed_constructor() {
+        let mut init = Some(from_closure(|slot| slot.write(()) as &mut OpqAny));
+        init.take();
+        drop(DynConstructor::new(&mut init).boxed());
+    }
+
+    #[test]
+    fn init_any_constructor() {
+        let inp = randstr(8..16);
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        let init = AnyConstructor::<dyn Any, 64>::from_constructor(init)
+            .ok()
+            .unwrap();
+        let out = init.boxed();
+        assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        assert!(AnyConstructor::<dyn Any, 0>::from_constructor(init).is_err());
+    }
+
+    #[test]
+    fn drop_unused_erased_constructors() {
+        let new_init = || {
+            let counter = DropCounter;
+            from_closure(move |slot| {
+                drop(counter);
+                slot.write(()) as &mut OpqAny
+            })
+        };
+
+        drop(Box::new(new_init()) as Box<dyn DynConstruct<Object = dyn Any>>);
+        assert_eq!(DropCounter::count(), 1);
+        let init = Box::new(new_init()) as Box<dyn DynConstruct<Object = dyn Any>>;
+        drop(init.boxed());
+        assert_eq!(DropCounter::count(), 2);
+
+        let init = AnyConstructor::<dyn Any, 16>::from_constructor(new_init());
+        drop(init.ok().unwrap());
+        assert_eq!(DropCounter::count(), 3);
+        let init = AnyConstructor::<dyn Any, 16>::from_constructor(new_init());
+        drop(init.ok().unwrap().boxed());
+        assert_eq!(DropCounter::count(), 4);
+    }
+
+    #[test]
+    #[should_panic = "container has been consumed"]
+    fn panic_on_consumed_container() {
+        let mut stk = newstk::<16>();
+        let mut container = Some(&mut stk);
+        let container: &mut DynContainer = &mut container;
+
+        let init = from_closure(|slot| slot.write(1) as &mut OpqAny);
+        let _ = init.try_init(&mut *container);
+        let init = from_closure(|slot| slot.write(2) as &mut OpqAny);
+        let _ = init.try_init(container);
+    }
+}
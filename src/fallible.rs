@@ -0,0 +1,194 @@
+use core::alloc::Layout;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::constructor::{init_failed, Opaque, Slot};
+use crate::container::TryReserve;
+
+/// A constructor whose construction may fail.
+///
+/// Unlike [`Construct`], the object is not guaranteed to be constructed, hence
+/// it can only be performed in containers that reserve memory ahead, i.e.,
+/// [`TryReserve`], which release the reserved memory if the construction
+/// fails. See [`TryDynify`] for the high-level interface.
+///
+/// # Safety
+///
+/// See the safety notes of [`Construct`]. Additionally, if [`try_construct`]
+/// fails, the provided slot must be left with no object to drop.
+///
+/// [`Construct`]: crate::Construct
+/// [`try_construct`]: Self::try_construct
+pub unsafe trait TryConstruct: Sized {
+    /// The type of objects to be constructed.
+    type Object: ?Sized;
+    /// The type of errors returned on failures.
+    type Error;
+
+    /// Returns the layout of the object to be constructed.
+    ///
+    /// See [`PinConstruct::layout`] for more information.
+    ///
+    /// [`PinConstruct::layout`]: crate::PinConstruct::layout
+    fn layout(&self) -> Layout;
+
+    /// Attempts to construct the object in the specified address.
+    ///
+    /// See [`PinConstruct::construct`] for more information.
+    ///
+    /// # Safety
+    ///
+    /// See the safety notes of [`PinConstruct::construct`].
+    ///
+    /// [`PinConstruct::construct`]: crate::PinConstruct::construct
+    unsafe fn try_construct(self, slot: Slot) -> Result<NonNull<Self::Object>, Self::Error>;
+}
+
+/// The main interface used to perform fallible in-place constructions.
+pub trait TryDynify: TryConstruct {
+    /// Constructs the object in the supplied container.
+    ///
+    /// For a non-panicking alternative see
+    /// [`try_init_fallible`](Self::try_init_fallible).
+    ///
+    /// # Panic
+    ///
+    /// It panics if `container` fails to reserve memory for the object.
+    fn init_fallible<C>(self, container: C) -> Result<C::Ptr, Self::Error>
+    where
+        C: TryReserve<Self::Object>,
+    {
+        self.try_init_fallible(container)
+            .unwrap_or_else(|(this, err)| init_failed(this.layout(), C::available(&err)))
+    }
+
+    /// Constructs the object in the supplied container.
+    ///
+    /// If `container` fails to reserve memory, `self` is returned along with
+    /// the encountered error. Otherwise, it returns the result of the
+    /// construction, releasing the reserved memory on failures.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_try_closure, TryDynify};
+    /// # use std::mem::MaybeUninit;
+    /// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+    /// let init = from_try_closure(|slot| "123".parse::<u64>().map(|v| slot.write(v)));
+    /// let val = init.try_init_fallible(&mut stack[..]).ok().unwrap();
+    /// assert_eq!(*val.unwrap(), 123);
+    ///
+    /// let init = from_try_closure(|slot| "xyz".parse::<u64>().map(|v| slot.write(v)));
+    /// let val = init.try_init_fallible(&mut stack[..]).ok().unwrap();
+    /// assert!(val.is_err());
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn try_init_fallible<C>(
+        self,
+        container: C,
+    ) -> Result<Result<C::Ptr, Self::Error>, (Self, C::Err)>
+    where
+        C: TryReserve<Self::Object>,
+    {
+        match container.reserve_slot(self.layout()) {
+            Ok(reserved) => match reserved.try_commit(self) {
+                Ok(res) => Ok(res),
+                Err(_) => panic!("layout of constructor changes"),
+            },
+            Err((_, e)) => Err((self, e)),
+        }
+    }
+
+    /// Constructs the object in [`Box`](alloc::boxed::Box).
+    ///
+    /// As long as there is enough free memory, it only fails if the
+    /// construction fails.
+    #[cfg(feature = "alloc")]
+    fn boxed_fallible(self) -> Result<alloc::boxed::Box<Self::Object>, Self::Error> {
+        self.try_init_fallible(crate::container::TryBoxed)
+            .unwrap_or_else(|(this, _)| alloc::alloc::handle_alloc_error(this.layout()))
+    }
+}
+impl<T: TryConstruct> TryDynify for T {}
+
+/// The constructor created by [`from_try_closure`].
+#[must_use = "constructor must be initialized"]
+pub struct TryClosure<T, F>(F, PhantomData<T>);
+// SAFETY: See the safety notes of `Closure`. Moreover, the slot is consumed
+// only if an object is written to it, in which case the object is leaked if
+// an error is returned afterwards.
+unsafe impl<T, U, E, F> TryConstruct for TryClosure<T, F>
+where
+    U: ?Sized,
+    F: FnOnce(Slot<T>) -> Result<&mut Opaque<U>, E>,
+{
+    type Object = U;
+    type Error = E;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn try_construct(self, slot: Slot) -> Result<NonNull<Self::Object>, Self::Error> {
+        let ptr = (self.0)(slot.cast())?;
+        Ok(NonNull::from(ptr.get_mut()))
+    }
+}
+
+/// Creates a new fallible closure constructor.
+///
+/// It works the same as [`from_closure`], except that `f` may return an error
+/// instead of filling the slot.
+///
+/// [`from_closure`]: crate::from_closure
+#[inline(always)]
+pub fn from_try_closure<T, U, E, F>(f: F) -> TryClosure<T, F>
+where
+    U: ?Sized,
+    F: FnOnce(Slot<T>) -> Result<&mut Opaque<U>, E>,
+{
+    TryClosure(f, PhantomData)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::mem::MaybeUninit;
+
+    use super::*;
+    use crate::utils::{newstk, randstr, DropCounter, OpqAny};
+
+    #[test]
+    fn init_fallible() {
+        let mut stk = newstk::<32>();
+        let inp = randstr(8..16);
+        let init = from_try_closure(|slot| Ok::<_, ()>(slot.write(inp.clone()) as &mut OpqAny));
+        let out = init.init_fallible(&mut stk[..]).unwrap();
+        assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+        drop(out);
+
+        let init = from_try_closure(|slot| Ok::<_, ()>(slot.write(inp.clone())));
+        let out = init.boxed_fallible().unwrap();
+        assert_eq!(*out, inp);
+    }
+
+    #[test]
+    fn release_memory_on_failure() {
+        let mut heap = Vec::<MaybeUninit<u8>>::new();
+        let init = from_try_closure(|_: Slot<[u8; 64]>| Err::<&mut OpqAny, _>("failed"));
+        let out = init.init_fallible(&mut heap);
+        assert_eq!(out.err(), Some("failed"));
+
+        let init = from_try_closure(|_: Slot<DropCounter>| Err::<&mut OpqAny, _>(()));
+        assert!(init.boxed_fallible().is_err());
+        assert_eq!(DropCounter::count(), 0);
+    }
+
+    #[test]
+    fn return_constructor_on_reservation_failure() {
+        let mut stk = newstk::<8>();
+        let inp = randstr(8..16);
+        let init = from_try_closure(|slot| Ok::<_, ()>(slot.write(inp.clone())));
+        let (init, err) = init.try_init_fallible(&mut stk[..]).unwrap_err();
+        assert_eq!(err.available(), 8);
+        assert_eq!(*init.boxed_fallible().unwrap(), inp);
+    }
+}
@@ -0,0 +1,152 @@
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+
+/// A pointer that runs a callback after its object is dropped.
+///
+/// It wraps another pointer, such as [`Buffered`], and dereferences to the
+/// object of that pointer. This is useful, for instance, to return pooled
+/// buffers or to record metrics once a dynamic future completes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify, Finally};
+/// # use std::cell::Cell;
+/// # use std::mem::MaybeUninit;
+/// # pollster::block_on(async {
+/// let completed = Cell::new(false);
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+/// let init = from_closure(|slot| slot.write(async { 123 }));
+/// let fut = init.init(&mut stack).with_drop_hook(|| completed.set(true));
+/// assert_eq!(fut.await, 123);
+/// assert!(completed.get());
+/// # });
+/// ```
+///
+/// [`Buffered`]: crate::Buffered
+pub struct Finally<P, F: FnOnce()> {
+    ptr: ManuallyDrop<P>,
+    hook: ManuallyDrop<F>,
+}
+impl<P, F: FnOnce()> Finally<P, F> {
+    /// Wraps `ptr`, running `hook` after it is dropped.
+    pub const fn new(ptr: P, hook: F) -> Self {
+        Self {
+            ptr: ManuallyDrop::new(ptr),
+            hook: ManuallyDrop::new(hook),
+        }
+    }
+
+    /// Consumes this instance without running the callback, returning the
+    /// inner pointer and the callback.
+    pub fn into_parts(this: Self) -> (P, F) {
+        let mut this = ManuallyDrop::new(this);
+        unsafe {
+            (
+                ManuallyDrop::take(&mut this.ptr),
+                ManuallyDrop::take(&mut this.hook),
+            )
+        }
+    }
+
+    /// Returns a pinned mutable reference to the inner pointer.
+    pub fn project(self: Pin<&mut Self>) -> Pin<&mut P> {
+        unsafe { self.map_unchecked_mut(|this| &mut *this.ptr) }
+    }
+}
+
+impl<P, F: FnOnce()> Drop for Finally<P, F> {
+    fn drop(&mut self) {
+        unsafe {
+            // Run the callback even if the object panics on drop.
+            let hook = ManuallyDrop::take(&mut self.hook);
+            let _guard = crate::utils::defer(hook);
+            ManuallyDrop::drop(&mut self.ptr);
+        }
+    }
+}
+// The callback is never pinned.
+impl<P: Unpin, F: FnOnce()> Unpin for Finally<P, F> {}
+
+impl<P: Deref, F: FnOnce()> Deref for Finally<P, F> {
+    type Target = P::Target;
+    fn deref(&self) -> &Self::Target {
+        &self.ptr
+    }
+}
+impl<P: DerefMut, F: FnOnce()> DerefMut for Finally<P, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.ptr
+    }
+}
+
+impl<P: fmt::Debug, F: FnOnce()> fmt::Debug for Finally<P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Finally").field(&*self.ptr).finish()
+    }
+}
+
+impl<P, F> core::future::Future for Finally<P, F>
+where
+    P: core::future::Future,
+    F: FnOnce(),
+{
+    type Output = P::Output;
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        self.project().poll(cx)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::utils::{newstk, randstr, DropCounter, OpqAny};
+    use crate::{from_closure, Dynify};
+
+    #[test]
+    fn run_hook_after_drop() {
+        let dropped = Cell::new(0);
+        let mut stk = newstk::<16>();
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let val = init.init(&mut stk).with_drop_hook(|| {
+            assert_eq!(DropCounter::count(), 1);
+            dropped.set(dropped.get() + 1);
+        });
+        assert!(val.is::<DropCounter>());
+        assert_eq!(dropped.get(), 0);
+        drop(val);
+        assert_eq!(dropped.get(), 1);
+    }
+
+    #[test]
+    fn disarm_hook() {
+        let inp = randstr(8..16);
+        let val = Finally::new(Box::new(inp.clone()), || panic!("hook is disarmed"));
+        let (val, _) = Finally::into_parts(val);
+        assert_eq!(*val, inp);
+    }
+
+    #[test]
+    fn run_hook_on_panic() {
+        struct PanicOnDrop;
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                panic!("dropped");
+            }
+        }
+
+        let dropped = Cell::new(false);
+        let val = Finally::new(Box::new(PanicOnDrop), || dropped.set(true));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(val)));
+        assert!(res.is_err());
+        assert!(dropped.get());
+    }
+}
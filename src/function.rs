@@ -1,14 +1,16 @@
 use core::alloc::Layout;
+use core::fmt;
 use core::marker::PhantomData;
 use core::ptr::NonNull;
 
-use crate::constructor::{Construct, Opaque, PinConstruct, Slot};
+use crate::constructor::{Construct, DebugConstruct, Opaque, PinConstruct, Slot};
+use crate::container::SizedFor;
 use crate::receiver::Receiver;
 
 /// A constructor for the return type of functions.
 #[must_use = "constructor must be initialized"]
 pub struct Fn<Args, Ret: ?Sized> {
-    layout: Layout,
+    object: &'static ObjectInfo,
     init: unsafe fn(Slot, Args) -> &mut Opaque<Ret>,
     args: Args,
 }
@@ -17,13 +19,54 @@ unsafe impl<Args, Ret: ?Sized> PinConstruct for Fn<Args, Ret> {
     type Object = Ret;
     unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
         let ptr = (self.init)(slot, self.args);
-        NonNull::from(ptr.as_mut())
+        NonNull::from(ptr.get_mut())
     }
     fn layout(&self) -> Layout {
-        self.layout
+        self.object.layout
     }
 }
 unsafe impl<Args, Ret: ?Sized> Construct for Fn<Args, Ret> {}
+impl<Args, Ret: ?Sized> DebugConstruct for Fn<Args, Ret> {
+    fn object_type_name(&self) -> &'static str {
+        (self.object.type_name)()
+    }
+}
+impl<Args, Ret: ?Sized> fmt::Debug for Fn<Args, Ret> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fn")
+            .field("object", &self.object_type_name())
+            .field("layout", &self.object.layout)
+            .finish_non_exhaustive()
+    }
+}
+impl<Args: Clone, Ret: ?Sized> Clone for Fn<Args, Ret> {
+    fn clone(&self) -> Self {
+        Self {
+            object: self.object,
+            init: self.init,
+            args: self.args.clone(),
+        }
+    }
+}
+
+/// The static information about the object of [`Fn`], shared by all
+/// constructors of the same object type to keep them small.
+struct ObjectInfo {
+    layout: Layout,
+    type_name: fn() -> &'static str,
+}
+impl ObjectInfo {
+    fn of<T>() -> &'static Self {
+        struct Of<T>(PhantomData<T>);
+        impl<T> Of<T> {
+            const INFO: &'static ObjectInfo = &ObjectInfo {
+                layout: Layout::new::<T>(),
+                type_name: core::any::type_name::<T>,
+            };
+        }
+        Of::<T>::INFO
+    }
+}
 
 /// A helper struct to display friendly errors.
 ///
@@ -51,7 +94,7 @@ where
     Ret: ?Sized,
 {
     Fn {
-        layout: Layout::new::<F::Ret>(),
+        object: ObjectInfo::of::<F::Ret>(),
         init,
         args,
     }
@@ -85,6 +128,73 @@ where
     from_bare_fn(|_| MethodAsBareFn::<Args, F>(PhantomData), args, init)
 }
 
+/// Creates a buffer for the return type of the specified function.
+#[inline(always)]
+pub fn buffer_for<F, Args>(_: fn(MustNotBeClosure) -> F) -> SizedFor<F::Ret>
+where
+    F: Function<Args>,
+{
+    SizedFor::new()
+}
+
+/// Returns the layout of the return type of the specified function.
+#[inline(always)]
+pub const fn layout_of<F, Args>(_: fn(MustNotBeClosure) -> F) -> Layout
+where
+    F: Function<Args>,
+{
+    Layout::new::<F::Ret>()
+}
+
+/// Deduces the signature of `make` from the type of `args`.
+#[inline(always)]
+pub fn async_maker<Args, Fut, G>(_: &Args, make: G) -> G
+where
+    G: FnOnce(Args) -> Fut,
+{
+    make
+}
+
+/// Creates a constructor for the future returned by `make`.
+///
+/// `make` is invoked with `args` when the returned instance is constructed,
+/// and `coerce` converts the initialized future into the target object.
+///
+/// # Safety
+///
+/// Both `make` and `coerce` must be closures that capture nothing.
+#[inline(always)]
+pub unsafe fn from_async<Args, Fut, Ret, G, C>(make: G, args: Args, coerce: C) -> Fn<Args, Ret>
+where
+    G: FnOnce(Args) -> Fut,
+    C: FnOnce(&mut Opaque<Fut>) -> &mut Opaque<Ret>,
+    Ret: ?Sized,
+{
+    /// Recreates a closure that captures nothing.
+    unsafe fn conjure<T>() -> T {
+        const { assert!(core::mem::size_of::<T>() == 0) };
+        NonNull::<T>::dangling().as_ptr().read()
+    }
+    unsafe fn init<Args, Fut, Ret, G, C>(slot: Slot<'_>, args: Args) -> &mut Opaque<Ret>
+    where
+        G: FnOnce(Args) -> Fut,
+        C: FnOnce(&mut Opaque<Fut>) -> &mut Opaque<Ret>,
+        Ret: ?Sized,
+    {
+        let (make, coerce) = (conjure::<G>(), conjure::<C>());
+        let ptr = slot.into_raw().cast::<Opaque<Fut>>();
+        ptr.as_ptr().write(Opaque::new(make(args)));
+        // `Fut` may not outlive the slot, so the object is reborrowed here.
+        &mut *(coerce(&mut *ptr.as_ptr()) as *mut Opaque<Ret>)
+    }
+    core::mem::forget((make, coerce));
+    Fn {
+        object: ObjectInfo::of::<Fut>(),
+        init: init::<Args, Fut, Ret, G, C>,
+        args,
+    }
+}
+
 /// A blanked trait implemented for arbitrary functions.
 pub trait Function<Args> {
     type Ret;
@@ -135,7 +245,18 @@ doc_macro! {
     /// The provided function must be a static item which can be resolved at
     /// compile-time; therefore, closures are not supported. For methods, the
     /// second parameter must be `self`; otherwise, the returned constructor
-    /// falls back to a bare function constructor.
+    /// falls back to a bare function constructor. Receivers that cannot be
+    /// declared as `self`, such as `Weak<Self>`, can be passed in the form of
+    /// `self = weak` instead.
+    ///
+    /// The function is invoked only when the constructor gets consumed, and its
+    /// return value is then moved into the container. Whether this move is
+    /// elided is up to the optimizer, which is not guaranteed by Rust. This is
+    /// usually cheap for async functions, since a future holds nothing but the
+    /// arguments before it is polled. However, a function returning a large
+    /// value may have its return value copied through the stack. In such cases,
+    /// consider [`from_closure`] with [`Slot::write_with`] to initialize the
+    /// object in place instead.
     ///
     /// # Example
     ///
@@ -147,16 +268,162 @@ doc_macro! {
     /// let _: Fn!(_ => dyn Future<Output = String>) = from_fn!(read_string, path);
     /// ```
     ///
+    /// A method that takes `self: Arc<Self>` may downgrade its receiver first:
+    ///
+    /// ```rust
+    /// # use dynify::{from_fn, Fn};
+    /// # use std::future::Future;
+    /// # use std::sync::{Arc, Weak};
+    /// struct Worker;
+    /// impl Worker {
+    ///     async fn run(this: Weak<Self>) -> bool { this.upgrade().is_some() }
+    ///     fn dyn_run(self: Arc<Self>) -> Fn!(Weak<Self> => dyn Future<Output = bool>) {
+    ///         let weak = Arc::downgrade(&self);
+    ///         from_fn!(Self::run, self = weak)
+    ///     }
+    /// }
+    /// ```
+    ///
     /// [`Fn`]: crate::Fn
+    /// [`from_closure`]: crate::from_closure
+    /// [`Slot::write_with`]: crate::Slot::write_with
     #[macro_export]
     macro from_fn {
+        ($f:expr, self = $recv:ident $(,$args:ident)*) => {};
         ($f:expr, $self:ident $(,$args:ident)*) => {};
         ($f:expr $(,$args:ident)*) => {};
     } {
+        ($f:expr, self = $recv:ident $(,$args:ident)* $(,)?) => { $crate::__from_fn!([self] $f, $recv, $($args,)*) };
         ($f:expr, $self:ident $(,$args:ident)* $(,)?) => { $crate::__from_fn!([$self] $f, $self, $($args,)*) };
         ($f:expr $(,$args:ident)* $(,)?) => { $crate::__from_fn!([] $f, $($args,)*) };
     }
 }
+/// Creates a [`SizedFor`] buffer for the return type of the specified function.
+///
+/// The size and alignment of the buffer are computed at compile time, so that
+/// constructors created by [`from_fn!`] for the same function never fail to be
+/// initialized in it. Like [`from_fn!`], the function must be a static item.
+/// See [`SizedFor`] for an example.
+///
+/// [`SizedFor`]: crate::SizedFor
+#[macro_export]
+macro_rules! buffer_for {
+    ($f:expr $(,)?) => {
+        $crate::r#priv::buffer_for(|_| $f)
+    };
+}
+
+/// Returns the [`Layout`] of the return type of the specified function.
+///
+/// The layout is computed at compile time, hence it can be used in constant
+/// contexts, e.g., to assert that a chosen buffer is large enough for the
+/// constructors created by [`from_fn!`] for that function. Like [`from_fn!`],
+/// the function must be a static item.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::layout_of;
+/// # use std::alloc::Layout;
+/// async fn read_string(path: &str) -> String {
+///     String::new()
+/// }
+///
+/// const LAYOUT: Layout = layout_of!(read_string);
+/// const _: () = assert!(LAYOUT.size() <= 64, "buffer is too small");
+/// ```
+///
+/// [`Layout`]: core::alloc::Layout
+#[macro_export]
+macro_rules! layout_of {
+    ($f:expr $(,)?) => {
+        $crate::r#priv::layout_of(|_| $f)
+    };
+}
+
+/// Creates a constructor for an async block.
+///
+/// It accepts as its parameters an async block followed by all the variables
+/// the block captures, returning a constructor for the future of that block.
+/// The listed variables are always moved into the future, while capturing any
+/// other variables leads to a compile error. This is a handy alternative to
+/// [`from_fn!`] that does not require a standalone function item. The type of
+/// returned constructors can be obtained with [`Async!`].
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_async, Async, PinDynify};
+/// # use std::future::Future;
+/// # pollster::block_on(async {
+/// let name = String::from("world");
+/// let times = 2;
+/// let init: Async!(String, usize => String) = from_async!(
+///     async { format!("Hello, {}!", name).repeat(times) },
+///     name,
+///     times,
+/// );
+/// assert_eq!(init.pin_boxed().await, "Hello, world!Hello, world!");
+/// # });
+/// ```
+///
+/// Variables that are not listed cannot be captured:
+///
+/// ```rust,compile_fail
+/// # use dynify::from_async;
+/// let name = String::from("world");
+/// let init = from_async!(async { name.len() });
+/// ```
+///
+/// [`Async!`]: crate::Async
+#[macro_export]
+macro_rules! from_async {
+    (async $(move)? $body:block $(,$args:ident)* $(,)?) => {{
+        let args = ($($args,)*);
+        let make = $crate::r#priv::async_maker(&args, |($($args,)*)| async move $body);
+        // Ensure that `make` captures nothing.
+        let _: fn(_) -> _ = make;
+        // SAFETY: Both `make` and the coercion closure capture nothing.
+        unsafe {
+            $crate::r#priv::from_async(make, args, |object| {
+                object as &mut $crate::Opaque::<_>
+            })
+        }
+    }};
+}
+
+doc_macro! {
+    /// Determines the constructor type of an async block.
+    ///
+    /// It accepts as its parameters a list of types of the captured variables
+    /// followed by a fat-arrow (`=>`) and the output type of the async block,
+    /// returning the type of constructors created by [`from_async!`]. The
+    /// object of the constructor is `dyn Future<Output = T>`, and additional
+    /// bounds of the trait object, such as lifetimes and auto traits, can be
+    /// specified after a semicolon.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use dynify::{from_async, Async};
+    /// # use std::future::Future;
+    /// fn count_words(text: &str) -> Async!(&str => usize; '_ + Send) {
+    ///     from_async!(async { text.split_whitespace().count() }, text)
+    /// }
+    /// ```
+    ///
+    /// [`from_async!`]: crate::from_async
+    #[macro_export]
+    macro Async {
+        ($($args:ty),* => $out:ty) => {};
+        ($($args:ty),* => $out:ty; $($bounds:tt)+) => {};
+    } {
+        ($($args:ty),* => $out:ty $(; $($bounds:tt)+)?) => {
+            $crate::r#priv::Fn<($($args,)*), dyn ::core::future::Future<Output = $out> $(+ $($bounds)+)?>
+        };
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __from_fn {
@@ -204,14 +471,16 @@ doc_macro! {
     /// function, returning the type of constructors created by [`from_fn`].
     ///
     /// For method types, which are functions with a receiver type such as
-    /// `&Self`, `&mut Self` or `Box<Self>` as the first parameter, this macro
-    /// automatically selects an appropriate sealed type to make the constructor
-    /// *dyn compatible*.
+    /// `&Self`, `&mut Self`, `Box<Self>` or `Weak<Self>` as the first parameter,
+    /// this macro automatically selects an appropriate sealed type to make the
+    /// constructor *dyn compatible*.
     ///
     /// Note that the receiver type should not include the full path. Using
     /// types like `std::boxed::Box<Self>` will lead to an incorrect matching
     /// and cause the constructor type to be *dyn incompatible*. Nevertheless,
-    /// it's not necessary to import these types beforehand.
+    /// it's not necessary to import these types beforehand. The only exception
+    /// is `rc::Weak<Self>`, which is required to tell it from `Weak<Self>` of
+    /// `std::sync`.
     ///
     /// If none of the supported receiver types matches, it falls back to a bare
     /// function type.
@@ -248,6 +517,9 @@ macro_rules! __Fn {
     (Box<Self>                  $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::BoxSelf, $($args,)*), $ret> };
     (Rc<Self>                   $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::RcSelf, $($args,)*), $ret> };
     (Arc<Self>                  $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::ArcSelf, $($args,)*), $ret> };
+    (Weak<Self>                 $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::WeakSelf, $($args,)*), $ret> };
+    (sync::Weak<Self>           $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::WeakSelf, $($args,)*), $ret> };
+    (rc::Weak<Self>             $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::RcWeakSelf, $($args,)*), $ret> };
 
     (Pin<&$($lt:lifetime)? Self>     $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::PinRefSelf$(<$lt>)*, $($args,)*), $ret> };
     (Pin<&$($lt:lifetime)? mut Self> $(,$args:ty)* => $ret:ty) => { $crate::r#priv::Fn<($crate::r#priv::PinRefMutSelf$(<$lt>)*, $($args,)*), $ret> };
@@ -36,6 +36,97 @@ fn return_type_layout_ok() {
     assert_eq!(layout(&f4), Layout::new::<Infallible>());
 }
 
+#[test]
+fn buffer_for_fn_ok() {
+    fn make(data: String) -> (String, [u64; 3]) {
+        (data, [0; 3])
+    }
+    let data = randstr(8..64);
+    let mut stack = buffer_for!(make);
+    assert_eq!(stack.capacity(), size_of::<(String, [u64; 3])>());
+
+    let arg = data.clone();
+    let init: Fn!(String => dyn Any) = from_fn!(make, arg);
+    let out = init.init(&mut stack);
+    let out = out.downcast_ref::<(String, [u64; 3])>().unwrap();
+    assert_eq!(out.0, data);
+}
+
+#[test]
+fn layout_of_fn_ok() {
+    async fn read(data: String) -> String {
+        data
+    }
+    fn make(_: String) -> (String, [u64; 3]) {
+        todo!()
+    }
+
+    const READ_LAYOUT: Layout = layout_of!(read);
+    const MAKE_LAYOUT: Layout = layout_of!(make);
+    assert_eq!(MAKE_LAYOUT, Layout::new::<(String, [u64; 3])>());
+
+    let data = randstr(8..64);
+    let init: Fn!(_ => StrFut) = from_fn!(read, data);
+    assert_eq!(init.layout(), READ_LAYOUT);
+}
+
+#[test]
+fn debug_fn_ok() {
+    fn make(_: usize) -> [u64; 3] {
+        todo!()
+    }
+
+    let arg = 0;
+    let init: Fn!(usize => dyn Any) = from_fn!(make, arg);
+    assert!(init.object_type_name().contains("u64"));
+    let msg = format!("{init:?}");
+    assert!(
+        msg.starts_with("Fn { object: ") && msg.contains("u64"),
+        "{msg}"
+    );
+}
+
+#[test]
+fn fn_stays_small() {
+    // The object information must not grow constructors of each function.
+    let size = std::mem::size_of::<Fn<(), dyn Any>>();
+    assert!(size <= std::mem::size_of::<(std::alloc::Layout, fn())>());
+}
+
+#[pollster::test]
+async fn clone_fn_ok() {
+    async fn read(data: String) -> String {
+        data
+    }
+
+    let data = randstr(8..64);
+    let arg = data.clone();
+    let init: Fn!(String => StrFut) = from_fn!(read, arg);
+    let init2 = init.clone();
+    assert_eq!(init.pin_boxed().await, data);
+    assert_eq!(init2.pin_boxed().await, data);
+}
+
+#[pollster::test]
+async fn from_async_ok() {
+    let data = randstr(8..64);
+    let (prefix, len) = (data.as_str(), data.len());
+    let init: Async!(&str, usize => String; '_ + Send) =
+        from_async!(async move { prefix[..len / 2].to_owned() }, prefix, len);
+    assert_eq!(init.pin_boxed().await, data[..data.len() / 2]);
+
+    let init: Async!(=> usize) = from_async!(async { 123usize });
+    assert_eq!(init.pin_boxed().await, 123);
+}
+
+#[test]
+fn drop_captured_variables() {
+    let counter = DropCounter;
+    let init: Async!(DropCounter => ()) = from_async!(async { drop(counter) }, counter);
+    drop(init);
+    assert_eq!(DropCounter::count(), 1);
+}
+
 #[pollster::test]
 async fn from_bare_fn_ok() {
     thread_local! {
@@ -88,19 +179,23 @@ impl Test {
     fn box_fn(self: Box<Self>) {}
     fn rc_fn(self: std::rc::Rc<Self>) {}
     fn arc_fn(self: std::sync::Arc<Self>) {}
+    fn weak_fn(_: std::sync::Weak<Self>) {}
+    fn rc_weak_fn(_: std::rc::Weak<Self>) {}
     fn pin_fn(self: Pin<&mut Self>) {}
     fn pin_box_fn(self: Pin<Box<Self>>) {}
 }
 
 #[rustfmt::skip]
     impl Test {
-        fn as_ref(&self)                      -> Fn!(&Self => ())          { from_fn!(Self::ref_fn, self) }
-        fn as_ref_mut(&mut self)              -> Fn!(&mut Self => ())      { from_fn!(Self::ref_mut_fn, self) }
-        fn as_box(self: Box<Self>)            -> Fn!(Box<Self> => ())      { from_fn!(Self::box_fn, self) }
-        fn as_rc(self: std::rc::Rc<Self>)     -> Fn!(Rc<Self> => ())       { from_fn!(Self::rc_fn, self) }
-        fn as_arc(self: std::sync::Arc<Self>) -> Fn!(Arc<Self> => ())      { from_fn!(Self::arc_fn, self) }
-        fn as_pin(self: Pin<&mut Self>)       -> Fn!(Pin<&mut Self> => ()) { from_fn!(Self::pin_fn, self) }
-        fn as_pin_box(self: Pin<Box<Self>>)   -> Fn!(Pin<Box<Self>> => ()) { from_fn!(Self::pin_box_fn, self) }
+        fn as_ref(&self)                       -> Fn!(&Self => ())          { from_fn!(Self::ref_fn, self) }
+        fn as_ref_mut(&mut self)               -> Fn!(&mut Self => ())      { from_fn!(Self::ref_mut_fn, self) }
+        fn as_box(self: Box<Self>)             -> Fn!(Box<Self> => ())      { from_fn!(Self::box_fn, self) }
+        fn as_rc(self: std::rc::Rc<Self>)      -> Fn!(Rc<Self> => ())       { from_fn!(Self::rc_fn, self) }
+        fn as_arc(self: std::sync::Arc<Self>)  -> Fn!(Arc<Self> => ())      { from_fn!(Self::arc_fn, self) }
+        fn as_weak(self: std::sync::Arc<Self>) -> Fn!(Weak<Self> => ())     { let weak = std::sync::Arc::downgrade(&self); from_fn!(Self::weak_fn, self = weak) }
+        fn as_rc_weak(self: std::rc::Rc<Self>) -> Fn!(rc::Weak<Self> => ()) { let weak = std::rc::Rc::downgrade(&self); from_fn!(Self::rc_weak_fn, self = weak) }
+        fn as_pin(self: Pin<&mut Self>)        -> Fn!(Pin<&mut Self> => ()) { from_fn!(Self::pin_fn, self) }
+        fn as_pin_box(self: Pin<Box<Self>>)    -> Fn!(Pin<Box<Self>> => ()) { from_fn!(Self::pin_box_fn, self) }
     }
 #[test]
 fn receiver_matching() {
@@ -109,6 +204,8 @@ fn receiver_matching() {
     let _: Fn<(BoxSelf,), ()> = Box::new(Test).as_box();
     let _: Fn<(RcSelf,), ()> = std::rc::Rc::new(Test).as_rc();
     let _: Fn<(ArcSelf,), ()> = std::sync::Arc::new(Test).as_arc();
+    let _: Fn<(WeakSelf,), ()> = std::sync::Arc::new(Test).as_weak();
+    let _: Fn<(RcWeakSelf,), ()> = std::rc::Rc::new(Test).as_rc_weak();
     let _: Fn<(crate::receiver::Pin<RefMutSelf>,), ()> = pin!(Test).as_pin();
     let _: Fn<(crate::receiver::Pin<BoxSelf>,), ()> = Box::pin(Test).as_pin_box();
 }
@@ -123,3 +220,29 @@ fn receiver_match_fallback() {
     let _: Fn<(&Test,), ()> = Test::as_bare_ref(&Test);
     let _: Fn<(Box<Test>,), ()> = Test::as_bare_box(Box::new(Test));
 }
+
+#[test]
+fn upgrade_weak_receiver() {
+    struct Node(String);
+    impl Node {
+        fn name(this: std::rc::Weak<Self>) -> Option<String> {
+            this.upgrade().map(|this| this.0.clone())
+        }
+        fn dyn_name(self: &std::rc::Rc<Self>) -> Fn!(rc::Weak<Self> => dyn Any) {
+            let weak = std::rc::Rc::downgrade(self);
+            from_fn!(Self::name, self = weak)
+        }
+    }
+
+    let data = randstr(8..64);
+    let node = std::rc::Rc::new(Node(data.clone()));
+    let init = node.dyn_name();
+    assert_eq!(std::rc::Rc::weak_count(&node), 1);
+    let out = init.boxed();
+    assert_eq!(out.downcast_ref::<Option<String>>(), Some(&Some(data)));
+    assert_eq!(std::rc::Rc::weak_count(&node), 0);
+
+    let init = node.dyn_name();
+    drop(node);
+    assert_eq!(init.boxed().downcast_ref::<Option<String>>(), Some(&None));
+}
@@ -0,0 +1,177 @@
+use core::alloc::Layout;
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::constructor::Construct;
+use crate::container::{
+    buf_can_fit, buf_emplace, Buffered, CapacityHint, Emplace, OutOfCapacity, PinEmplace,
+};
+use crate::container_core::construct_in;
+
+/// A fixed-size buffer that can be placed in a `static`.
+///
+/// The buffer can be claimed only once: the first successful construction
+/// takes the whole buffer forever, and subsequent constructions fail with
+/// [`OutOfCapacity`]. The claim is checked atomically, so the buffer can be
+/// shared across threads. This makes it a global fallback container for
+/// `no_std` environments without heap allocation.
+///
+/// Since the buffer is never reused, a `'static` reference to it can also be
+/// used as a pinned container.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, Dynify, Fn, StaticBuffer};
+/// # use std::future::Future;
+/// static BUFFER: StaticBuffer<64> = StaticBuffer::new();
+///
+/// # pollster::block_on(async {
+/// let constructor: Fn!(=> dyn Future<Output = i32>) = from_fn!(|| async { 777 });
+/// assert_eq!(constructor.init(&BUFFER).await, 777);
+/// assert!(BUFFER.is_claimed());
+/// # });
+/// ```
+pub struct StaticBuffer<const N: usize> {
+    claimed: AtomicBool,
+    buf: UnsafeCell<[MaybeUninit<u8>; N]>,
+}
+impl<const N: usize> StaticBuffer<N> {
+    /// Creates a new buffer of `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            claimed: AtomicBool::new(false),
+            buf: UnsafeCell::new([MaybeUninit::uninit(); N]),
+        }
+    }
+
+    /// Returns the number of bytes this buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns whether this buffer has been claimed.
+    pub fn is_claimed(&self) -> bool {
+        self.claimed.load(Ordering::Acquire)
+    }
+}
+impl<const N: usize> Default for StaticBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> fmt::Debug for StaticBuffer<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticBuffer")
+            .field("capacity", &N)
+            .field("claimed", &self.is_claimed())
+            .finish()
+    }
+}
+
+// SAFETY: The memory is accessed only by the first claimer.
+unsafe impl<const N: usize> Sync for StaticBuffer<N> {}
+
+unsafe impl<'a, T: 'a + ?Sized, const N: usize> Emplace<T> for &'a StaticBuffer<N> {
+    type Ptr = Buffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let layout = constructor.layout();
+            if self.claimed.swap(true, Ordering::AcqRel) {
+                return Err(OutOfCapacity::new(layout, 0));
+            }
+            // Unclaim the buffer if the construction fails or panics.
+            let unclaim = crate::utils::defer(|| self.claimed.store(false, Ordering::Release));
+
+            let slot = buf_emplace(&mut *self.buf.get(), layout)?;
+            let init = construct_in(slot, constructor, layout);
+
+            core::mem::forget(unclaim);
+            Ok(Buffered::from_raw(init))
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+// SAFETY: A claimed buffer is never reused, and a `'static` buffer is never
+// released.
+unsafe impl<T: 'static + ?Sized, const N: usize> PinEmplace<T> for &'static StaticBuffer<N> {}
+impl<const N: usize> CapacityHint for &StaticBuffer<N> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        !self.is_claimed() && buf_can_fit(self.buf.get().cast(), N, layout)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::marker::PhantomPinned;
+    use std::pin::Pin;
+
+    use super::*;
+    use crate::utils::{randarr, OpqAny};
+    use crate::{from_closure, Dynify, PinDynify};
+
+    #[test]
+    fn claim_only_once() {
+        let buf = StaticBuffer::<64>::new();
+        let init = from_closure(|slot| slot.write(123) as &mut OpqAny);
+        let out = init.init(&buf);
+        assert_eq!(out.downcast_ref::<i32>(), Some(&123));
+        assert!(buf.is_claimed());
+
+        drop(out);
+        let init = from_closure(|slot| slot.write(456) as &mut OpqAny);
+        assert!(init.try_init(&buf).is_err());
+    }
+
+    #[test]
+    fn unclaim_on_failure() {
+        let buf = StaticBuffer::<16>::new();
+        let inp = randarr::<32>();
+        let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+        let (init, _) = init.try_init(&buf).unwrap_err();
+        assert!(!buf.is_claimed());
+        drop(init);
+
+        let init = from_closure::<usize, usize, _>(|_| panic!("just panic"));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| init.init(&buf)));
+        assert!(res.is_err());
+        assert!(!buf.is_claimed());
+    }
+
+    #[test]
+    fn pin_init_static_buffer() {
+        static BUFFER: StaticBuffer<8> = StaticBuffer::new();
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let _: Pin<Buffered<PhantomPinned>> = init.pin_init(&BUFFER);
+    }
+
+    #[test]
+    fn share_across_threads() {
+        static BUFFER: StaticBuffer<8> = StaticBuffer::new();
+        let claimed = std::thread::scope(|s| {
+            let handles = (0..4)
+                .map(|i| {
+                    s.spawn(move || {
+                        let init = from_closure(|slot| slot.write(i));
+                        init.try_init(&BUFFER).map(|p| *p).ok()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .filter_map(|h| h.join().unwrap())
+                .count()
+        });
+        assert_eq!(claimed, 1);
+    }
+}
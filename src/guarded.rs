@@ -0,0 +1,155 @@
+use core::alloc::Layout;
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, PinConstruct, Slot};
+
+/// Callbacks invoked around the construction of [`guarded`] constructors.
+///
+/// All methods do nothing by default. Exactly one of [`after`] and
+/// [`on_panic`] is invoked once [`before`] returns.
+///
+/// [`after`]: Self::after
+/// [`on_panic`]: Self::on_panic
+/// [`before`]: Self::before
+pub trait ConstructHook: Sized {
+    /// Invoked right before the object is constructed with its layout.
+    fn before(&mut self, layout: Layout) {
+        let _ = layout;
+    }
+
+    /// Invoked after the object is constructed.
+    fn after(self) {}
+
+    /// Invoked while unwinding if the construction panics.
+    fn on_panic(self) {}
+}
+
+/// The constructor created by [`guarded`].
+#[must_use = "constructor must be initialized"]
+pub struct Guarded<C, H>(C, H);
+unsafe impl<C, H> PinConstruct for Guarded<C, H>
+where
+    C: PinConstruct,
+    H: ConstructHook,
+{
+    type Object = C::Object;
+    fn layout(&self) -> Layout {
+        self.0.layout()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let Self(init, mut hook) = self;
+        hook.before(init.layout());
+        let guard = OnPanic(ManuallyDrop::new(hook));
+        let ptr = init.construct(slot);
+        guard.disarm().after();
+        ptr
+    }
+}
+unsafe impl<C, H> Construct for Guarded<C, H>
+where
+    C: Construct,
+    H: ConstructHook,
+{
+}
+
+/// Runs [`ConstructHook::on_panic`] unless disarmed.
+struct OnPanic<H: ConstructHook>(ManuallyDrop<H>);
+impl<H: ConstructHook> OnPanic<H> {
+    fn disarm(self) -> H {
+        let mut this = ManuallyDrop::new(self);
+        unsafe { ManuallyDrop::take(&mut this.0) }
+    }
+}
+impl<H: ConstructHook> Drop for OnPanic<H> {
+    fn drop(&mut self) {
+        unsafe { ManuallyDrop::take(&mut self.0).on_panic() }
+    }
+}
+
+/// Wraps a constructor, invoking the callbacks of `hook` around its
+/// construction.
+///
+/// This is useful to enter tracing spans or to record metrics for every
+/// in-place construction, without changing the code where the constructor is
+/// consumed. The returned constructor has the same layout and object as `init`.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_value, guarded, ConstructHook, Dynify};
+/// # use std::alloc::Layout;
+/// # use std::cell::Cell;
+/// struct Account<'a>(&'a Cell<usize>);
+/// impl ConstructHook for Account<'_> {
+///     fn before(&mut self, layout: Layout) {
+///         self.0.set(self.0.get() + layout.size());
+///     }
+/// }
+///
+/// let allocated = Cell::new(0);
+/// let val = guarded(from_value(123u64), Account(&allocated)).boxed();
+/// assert_eq!(*val, 123);
+/// assert_eq!(allocated.get(), 8);
+/// ```
+#[inline(always)]
+pub fn guarded<C, H>(init: C, hook: H) -> Guarded<C, H>
+where
+    C: PinConstruct,
+    H: ConstructHook,
+{
+    Guarded(init, hook)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::utils::{newstk, randstr, OpqAny};
+    use crate::{from_closure, from_value, Dynify, PinDynify};
+
+    struct Recorder<'a>(&'a RefCell<Vec<&'static str>>);
+    impl ConstructHook for Recorder<'_> {
+        fn before(&mut self, layout: Layout) {
+            assert_eq!(layout, Layout::new::<String>());
+            self.0.borrow_mut().push("before");
+        }
+        fn after(self) {
+            self.0.borrow_mut().push("after");
+        }
+        fn on_panic(self) {
+            self.0.borrow_mut().push("on_panic");
+        }
+    }
+
+    #[test]
+    fn invoke_hooks_around_construction() {
+        let events = RefCell::new(Vec::new());
+        let mut stk = newstk::<32>();
+        let inp = randstr(8..16);
+        let init = guarded(from_value(inp.clone()), Recorder(&events));
+        assert_eq!(init.layout(), Layout::new::<String>());
+        assert!(events.borrow().is_empty());
+        assert_eq!(*init.init(&mut stk), inp);
+        assert_eq!(*events.borrow(), ["before", "after"]);
+
+        events.borrow_mut().clear();
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        let out = guarded(init, Recorder(&events)).pin_boxed();
+        assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+        assert_eq!(*events.borrow(), ["before", "after"]);
+    }
+
+    #[test]
+    fn invoke_hook_on_panic() {
+        let events = RefCell::new(Vec::new());
+        let init = from_closure::<String, dyn Any, _>(|_| panic!("construction panicked"));
+        let init = guarded(init, Recorder(&events));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(init.boxed())));
+        assert!(res.is_err());
+        assert_eq!(*events.borrow(), ["before", "on_panic"]);
+    }
+}
@@ -0,0 +1,216 @@
+use core::alloc::Layout;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, Slot};
+use crate::container::{dangling_slot, AllocError, CapacityHint, Emplace, PinEmplace};
+use crate::container_core::construct_in;
+
+/// A unit type to perform constructions with the global allocator.
+///
+/// It works the same as [`TryBoxed`], except that it yields [`Allocated`]
+/// rather than [`Box`]. This is useful for environments where `Box` is not
+/// desired in public APIs, e.g., when the ownership is handed over through raw
+/// pointers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Allocated, Dynify, Heap};
+/// # use std::any::Any;
+/// let init = from_closure(|slot| slot.write(String::from("Hello!")) as &mut _);
+/// let val: Allocated<dyn Any> = init.init(Heap);
+/// let raw = Allocated::into_raw(val);
+/// let val = unsafe { Allocated::from_raw(raw) };
+/// assert_eq!(val.downcast_ref::<String>().unwrap(), "Hello!");
+/// ```
+///
+/// [`Box`]: alloc::boxed::Box
+/// [`TryBoxed`]: crate::TryBoxed
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct Heap;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+unsafe impl<T: ?Sized> Emplace<T> for Heap {
+    type Ptr = Allocated<T>;
+    type Err = AllocError;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let layout = constructor.layout();
+            let ptr = if layout.size() == 0 {
+                dangling_slot(layout).as_ptr()
+            } else {
+                NonNull::new(alloc::alloc::alloc(layout)).ok_or(AllocError::new(layout))?
+            };
+
+            // Recycle the allocated memory to prevent memory leaks if
+            // `construct()` panics.
+            let clean_on_panic = crate::utils::defer(|| dealloc(ptr, layout));
+            let init = construct_in(Slot::new_unchecked(ptr), constructor, layout);
+
+            core::mem::forget(clean_on_panic);
+            Ok(Allocated::from_raw(init))
+        }
+    }
+}
+// SAFETY: The object is never moved until it's dropped.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+unsafe impl<T: ?Sized> PinEmplace<T> for Heap {}
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl CapacityHint for Heap {
+    fn can_fit(&self, _: Layout) -> bool {
+        true
+    }
+}
+
+unsafe fn dealloc(ptr: NonNull<u8>, layout: Layout) {
+    if layout.size() != 0 {
+        alloc::alloc::dealloc(ptr.as_ptr(), layout)
+    }
+}
+
+/// A pointer to objects allocated by the global allocator.
+///
+/// It is yielded by [`Heap`] and owns the object along with its memory block,
+/// just like [`Box`]. The ownership can be transferred through raw pointers
+/// with [`into_raw`](Self::into_raw) and [`from_raw`](Self::from_raw). Similar
+/// to [`Buffered`], it implements `Unpin` only if `T` is `Unpin`.
+///
+/// [`Box`]: alloc::boxed::Box
+/// [`Buffered`]: crate::Buffered
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Allocated<T: ?Sized>(NonNull<T>, PhantomData<T>);
+impl<T: ?Sized> Allocated<T> {
+    /// Creates a new instance from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be obtained from [`Allocated::into_raw`].
+    pub unsafe fn from_raw(ptr: NonNull<T>) -> Self {
+        Self(ptr, PhantomData)
+    }
+
+    /// Consumes this instance, returning a raw pointer.
+    ///
+    /// The object is neither dropped nor deallocated. To release it, convert
+    /// the pointer back with [`Allocated::from_raw`].
+    pub fn into_raw(this: Self) -> NonNull<T> {
+        let ptr = this.0;
+        core::mem::forget(this);
+        ptr
+    }
+
+    /// Returns a pinned mutable reference to the inner value.
+    pub fn project(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe { self.map_unchecked_mut(|this| &mut **this) }
+    }
+
+    /// Returns a pinned immutable reference to the inner value.
+    pub fn project_ref(self: Pin<&Self>) -> Pin<&T> {
+        unsafe { self.map_unchecked(|this| &**this) }
+    }
+}
+
+// SAFETY: We own the value of `T`.
+unsafe impl<T: ?Sized + Send> Send for Allocated<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Allocated<T> {}
+
+impl<T: ?Sized> Drop for Allocated<T> {
+    fn drop(&mut self) {
+        // Release the memory even if the destructor panics.
+        let ptr = self.0;
+        let layout = unsafe { Layout::for_value(ptr.as_ref()) };
+        let _dealloc = crate::utils::defer(|| unsafe { dealloc(ptr.cast(), layout) });
+        if core::mem::needs_drop::<T>() {
+            unsafe { ptr.drop_in_place() }
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for Allocated<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.0.as_ref() }
+    }
+}
+impl<T: ?Sized> DerefMut for Allocated<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.0.as_mut() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Allocated<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> core::future::Future for Allocated<T>
+where
+    T: ?Sized + core::future::Future,
+{
+    type Output = T::Output;
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        self.project().poll(cx)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::marker::PhantomPinned;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::utils::{randarr, randstr, DropCounter, OpqAny, OpqStrFut};
+    use crate::{from_closure, Dynify, PinDynify};
+
+    #[rstest]
+    #[case(randstr(8..64))]
+    #[case(randarr::<0>())]
+    #[case(randarr::<33>())]
+    #[case(fastrand::u128(..))]
+    fn init_in_heap<T: Any + Clone + PartialEq + std::fmt::Debug>(#[case] inp: T) {
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        let out: Allocated<dyn Any> = init.init(Heap);
+        assert_eq!(out.downcast_ref::<T>(), Some(&inp));
+    }
+
+    #[test]
+    fn transfer_raw_ownership() {
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let raw = Allocated::into_raw(init.init(Heap));
+        assert_eq!(DropCounter::count(), 0);
+        drop(unsafe { Allocated::from_raw(raw) });
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[test]
+    #[should_panic = "just panic"]
+    fn clean_up_heap_on_panic() {
+        let _ = from_closure::<usize, usize, _>(|_| panic!("just panic")).init(Heap);
+    }
+
+    #[pollster::test]
+    async fn pin_init_in_heap() {
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let _ = init.pin_init(Heap);
+
+        let inp = randstr(8..64);
+        let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+        assert_eq!(init.pin_init(Heap).await, inp);
+    }
+}
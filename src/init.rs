@@ -0,0 +1,168 @@
+use core::alloc::Layout;
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use pin_init::{Init, PinInit};
+
+use crate::constructor::{Construct, PinConstruct, Slot};
+use crate::fallible::TryConstruct;
+
+/// The constructor created by [`from_init`].
+#[cfg_attr(docsrs, doc(cfg(feature = "pin-init")))]
+#[must_use = "constructor must be initialized"]
+pub struct Initializer<T, E, I>(I, PhantomData<fn() -> Result<T, E>>);
+unsafe impl<T, I> PinConstruct for Initializer<T, Infallible, I>
+where
+    I: Init<T, Infallible>,
+{
+    type Object = T;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        match TryConstruct::try_construct(self, slot) {
+            Ok(ptr) => ptr,
+            Err(e) => match e {},
+        }
+    }
+}
+unsafe impl<T, I> Construct for Initializer<T, Infallible, I> where I: Init<T, Infallible> {}
+// SAFETY: `Init` guarantees that the slot is left uninitialized on failures.
+unsafe impl<T, E, I> TryConstruct for Initializer<T, E, I>
+where
+    I: Init<T, E>,
+{
+    type Object = T;
+    type Error = E;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn try_construct(self, slot: Slot) -> Result<NonNull<Self::Object>, Self::Error> {
+        let ptr = slot.into_raw().cast::<T>();
+        self.0.__init(ptr.as_ptr())?;
+        Ok(ptr)
+    }
+}
+
+/// Creates a new constructor from an initializer of [`pin-init`].
+///
+/// The returned constructor implements [`Construct`] if `init` is infallible,
+/// and [`TryConstruct`] otherwise. For initializers that require pinned memory
+/// blocks, see [`from_pin_init`].
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_init, Dynify, TryDynify};
+/// # use std::convert::Infallible;
+/// let init = unsafe {
+///     pin_init::init_from_closure(|slot: *mut u64| Ok::<_, Infallible>(slot.write(123)))
+/// };
+/// assert_eq!(*from_init(init).boxed(), 123);
+///
+/// let init = unsafe { pin_init::init_from_closure(|_: *mut u64| Err("failed")) };
+/// assert_eq!(from_init(init).boxed_fallible().err(), Some("failed"));
+/// ```
+///
+/// [`pin-init`]: https://docs.rs/pin-init
+#[cfg_attr(docsrs, doc(cfg(feature = "pin-init")))]
+#[inline(always)]
+pub fn from_init<T, E, I>(init: I) -> Initializer<T, E, I>
+where
+    I: Init<T, E>,
+{
+    Initializer(init, PhantomData)
+}
+
+/// The constructor created by [`from_pin_init`].
+#[cfg_attr(docsrs, doc(cfg(feature = "pin-init")))]
+#[must_use = "constructor must be initialized"]
+pub struct PinInitializer<T, I>(I, PhantomData<fn() -> T>);
+unsafe impl<T, I> PinConstruct for PinInitializer<T, I>
+where
+    I: PinInit<T, Infallible>,
+{
+    type Object = T;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.into_raw().cast::<T>();
+        match self.0.__pinned_init(ptr.as_ptr()) {
+            Ok(()) => ptr,
+            Err(e) => match e {},
+        }
+    }
+}
+
+/// Creates a new constructor from a pinned initializer of [`pin-init`].
+///
+/// The returned constructor implements [`PinConstruct`] only, and hence can
+/// only be used with pinned containers. Since fallible constructions are not
+/// supported in pinned containers yet, `init` must be infallible.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_pin_init, PinDynify};
+/// # use std::convert::Infallible;
+/// let init = unsafe {
+///     pin_init::pin_init_from_closure(|slot: *mut u64| Ok::<_, Infallible>(slot.write(123)))
+/// };
+/// assert_eq!(*from_pin_init(init).pin_boxed(), 123);
+/// ```
+///
+/// [`pin-init`]: https://docs.rs/pin-init
+#[cfg_attr(docsrs, doc(cfg(feature = "pin-init")))]
+#[inline(always)]
+pub fn from_pin_init<T, I>(init: I) -> PinInitializer<T, I>
+where
+    I: PinInit<T, Infallible>,
+{
+    PinInitializer(init, PhantomData)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::utils::{newstk, randstr, DropCounter};
+    use crate::{Dynify, PinDynify, TryDynify};
+
+    #[test]
+    fn init_from_init() {
+        let mut stk = newstk::<32>();
+        let inp = randstr(8..16);
+        let init = from_init::<_, Infallible, _>(inp.clone());
+        assert_eq!(*init.init(&mut stk), inp);
+
+        let init = unsafe {
+            pin_init::init_from_closure(|slot: *mut String| {
+                slot.write(inp.clone());
+                Ok::<_, ()>(())
+            })
+        };
+        assert_eq!(from_init(init).boxed_fallible(), Ok(Box::new(inp)));
+    }
+
+    #[test]
+    fn release_memory_on_failure() {
+        let init = unsafe { pin_init::init_from_closure(|_: *mut DropCounter| Err(())) };
+        assert!(from_init(init).boxed_fallible().is_err());
+        assert_eq!(DropCounter::count(), 0);
+    }
+
+    #[test]
+    fn init_from_pin_init() {
+        let inp = randstr(8..16);
+        let init = unsafe {
+            pin_init::pin_init_from_closure(|slot: *mut String| {
+                slot.write(inp.clone());
+                Ok::<_, Infallible>(())
+            })
+        };
+        let mut stk = crate::pin_buffer!(32);
+        assert_eq!(*from_pin_init(init).pin_init(stk.as_mut()), inp);
+    }
+}
@@ -0,0 +1,449 @@
+use core::alloc::Layout;
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, Slot};
+use crate::container::{CapacityHint, Emplace, OutOfCapacity};
+use crate::container_core::construct_in;
+
+/// An owned container that stores objects inline.
+///
+/// Unlike other fixed-size buffers, this container is consumed by value, and
+/// the returned [`Inlined`] pointer owns the storage. Therefore, an object can
+/// be constructed without heap allocation and still be returned from the
+/// function that constructs it. The storage is `N` bytes aligned to `A`, and
+/// objects of a larger size or alignment are rejected with [`OutOfCapacity`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, Dynify, Fn, Inline, Inlined};
+/// # use std::future::Future;
+/// fn make_fut(n: i32) -> Inlined<dyn Future<Output = i32>, 16> {
+///     let constructor: Fn!(i32 => dyn Future<Output = i32>) =
+///         from_fn!(|n| async move { n * 2 }, n);
+///     constructor.init(Inline::new())
+/// }
+/// # pollster::block_on(async {
+/// assert_eq!(make_fut(7).await, 14);
+/// # });
+/// ```
+pub struct Inline<const N: usize, A = usize>(PhantomData<A>);
+impl<const N: usize, A> Inline<N, A> {
+    /// Creates a new container of `N` bytes.
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+impl<const N: usize, A> Default for Inline<N, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize, A> fmt::Debug for Inline<N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inline")
+            .field("capacity", &N)
+            .field("align", &core::mem::align_of::<A>())
+            .finish()
+    }
+}
+
+unsafe impl<T: ?Sized, const N: usize, A> Emplace<T> for Inline<N, A> {
+    type Ptr = Inlined<T, N, A>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let layout = constructor.layout();
+            if !self.can_fit(layout) {
+                return Err(OutOfCapacity::new(layout, N));
+            }
+
+            // The storage is aligned to `A`, so objects are always placed at
+            // the start of it, including ZSTs.
+            let storage = Storage::<N, A>::new();
+            let ptr = storage.as_ptr();
+            let slot = Slot::new_unchecked(ptr);
+
+            let init = construct_in(slot, constructor, layout);
+            // The object is moved along with the storage, which is fine since
+            // `Construct` does not rely on a pinned memory block.
+            Ok(Inlined::new(init, storage))
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+
+impl<const N: usize, A> CapacityHint for Inline<N, A> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        layout.size() <= N && layout.align() <= core::mem::align_of::<A>()
+    }
+}
+
+#[repr(C)]
+struct Storage<const N: usize, A> {
+    _align: [A; 0],
+    buf: UnsafeCell<[MaybeUninit<u8>; N]>,
+}
+impl<const N: usize, A> Storage<N, A> {
+    fn new() -> Self {
+        Self {
+            _align: [],
+            buf: UnsafeCell::new([MaybeUninit::uninit(); N]),
+        }
+    }
+
+    fn as_ptr(&self) -> NonNull<u8> {
+        unsafe { NonNull::new_unchecked(self.buf.get().cast()) }
+    }
+
+    /// Returns a raw pointer to the object placed at the start of this storage
+    /// with the metadata of `meta`, whose address may be out of date.
+    fn object<T: ?Sized>(&self, meta: NonNull<T>) -> *mut T {
+        crate::utils::with_addr(meta.as_ptr(), self.as_ptr().as_ptr())
+    }
+}
+
+/// A pointer to objects stored inline.
+///
+/// It is yielded by [`Inline`] and owns the storage of the object, which means
+/// moving this pointer moves the object as well. Similar to [`Buffered`], it
+/// implements `Unpin` only if `T` is `Unpin`, so that it can be pinned in place
+/// and projected to a pinned reference to `T`.
+///
+/// [`Buffered`]: crate::Buffered
+pub struct Inlined<T: ?Sized, const N: usize, A = usize> {
+    // Holds the metadata of `T`, while its address is out of date once moved.
+    meta: NonNull<T>,
+    storage: Storage<N, A>,
+    _marker: PhantomData<T>,
+}
+impl<T: ?Sized, const N: usize, A> Inlined<T, N, A> {
+    unsafe fn new(meta: NonNull<T>, storage: Storage<N, A>) -> Self {
+        Self {
+            meta,
+            storage,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a raw pointer to the object at its current address.
+    pub(crate) fn as_ptr(&self) -> *mut T {
+        self.storage.object(self.meta)
+    }
+
+    /// Returns a pinned mutable reference to the inner value.
+    pub fn project(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe { self.map_unchecked_mut(|this| &mut **this) }
+    }
+
+    /// Returns a pinned immutable reference to the inner value.
+    pub fn project_ref(self: Pin<&Self>) -> Pin<&T> {
+        unsafe { self.map_unchecked(|this| &**this) }
+    }
+}
+impl<T, const N: usize, A> Inlined<T, N, A> {
+    /// Consumes this instance, returning the inner value.
+    pub fn into_inner(self) -> T {
+        let this = core::mem::ManuallyDrop::new(self);
+        unsafe { this.as_ptr().read() }
+    }
+}
+
+// SAFETY: We own the value of `T`.
+unsafe impl<T: ?Sized + Send, const N: usize, A> Send for Inlined<T, N, A> {}
+unsafe impl<T: ?Sized + Sync, const N: usize, A> Sync for Inlined<T, N, A> {}
+
+impl<T: ?Sized, const N: usize, A> Drop for Inlined<T, N, A> {
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<T>() {
+            unsafe { self.as_ptr().drop_in_place() }
+        }
+    }
+}
+
+impl<T: ?Sized, const N: usize, A> Deref for Inlined<T, N, A> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.as_ptr() }
+    }
+}
+impl<T: ?Sized, const N: usize, A> DerefMut for Inlined<T, N, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.as_ptr() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, const N: usize, A> fmt::Debug for Inlined<T, N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T, const N: usize, A> core::future::Future for Inlined<T, N, A>
+where
+    T: ?Sized + core::future::Future,
+{
+    type Output = T::Output;
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        self.project().poll(cx)
+    }
+}
+
+/// A one-shot container embedded in other types.
+///
+/// Like [`Inline`], it stores an object of up to `N` bytes aligned to `A`
+/// inline, but the storage lives in this slot instead of the returned pointer.
+/// It accepts only one successful construction, after which it dereferences to
+/// the constructed object for the rest of its lifetime. This makes it a
+/// building block for lazily initialized trait objects stored in struct fields
+/// without heap allocation. Moving the slot moves the object along with it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, Dynify, OnceSlot};
+/// # use std::fmt::Display;
+/// struct Greeter {
+///     name: OnceSlot<dyn Display, 16>,
+/// }
+///
+/// let mut greeter = Greeter {
+///     name: OnceSlot::new(),
+/// };
+/// from_closure(|slot| slot.write("world") as &mut _).init(&mut greeter.name);
+///
+/// let greeter = std::convert::identity(greeter);
+/// assert_eq!(greeter.name.to_string(), "world");
+/// ```
+pub struct OnceSlot<T: ?Sized, const N: usize, A = usize> {
+    // Holds the metadata of `T`, while its address is out of date once moved.
+    meta: Option<NonNull<T>>,
+    storage: Storage<N, A>,
+    _marker: PhantomData<T>,
+}
+impl<T: ?Sized, const N: usize, A> OnceSlot<T, N, A> {
+    /// Creates a new empty slot of `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            meta: None,
+            storage: Storage {
+                _align: [],
+                buf: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if an object has been constructed in this slot.
+    pub fn is_initialized(&self) -> bool {
+        self.meta.is_some()
+    }
+
+    /// Returns a reference to the constructed object, or `None` if this slot
+    /// is empty.
+    pub fn get(&self) -> Option<&T> {
+        self.meta.map(|meta| unsafe { &*self.storage.object(meta) })
+    }
+
+    /// Returns a mutable reference to the constructed object, or `None` if
+    /// this slot is empty.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.meta
+            .map(|meta| unsafe { &mut *self.storage.object(meta) })
+    }
+}
+impl<T: ?Sized, const N: usize, A> Default for OnceSlot<T, N, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: We own the value of `T`.
+unsafe impl<T: ?Sized + Send, const N: usize, A> Send for OnceSlot<T, N, A> {}
+unsafe impl<T: ?Sized + Sync, const N: usize, A> Sync for OnceSlot<T, N, A> {}
+
+impl<T: ?Sized, const N: usize, A> Drop for OnceSlot<T, N, A> {
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<T>() {
+            if let Some(val) = self.get_mut() {
+                unsafe { core::ptr::drop_in_place(val) }
+            }
+        }
+    }
+}
+
+/// # Panic
+///
+/// It panics if this slot is empty.
+impl<T: ?Sized, const N: usize, A> Deref for OnceSlot<T, N, A> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.get().expect("slot is not initialized")
+    }
+}
+/// # Panic
+///
+/// It panics if this slot is empty.
+impl<T: ?Sized, const N: usize, A> DerefMut for OnceSlot<T, N, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut().expect("slot is not initialized")
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, const N: usize, A> fmt::Debug for OnceSlot<T, N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OnceSlot").field(&self.get()).finish()
+    }
+}
+
+unsafe impl<'a, T: ?Sized, const N: usize, A> Emplace<T> for &'a mut OnceSlot<T, N, A> {
+    type Ptr = &'a mut T;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let layout = constructor.layout();
+            if !self.can_fit(layout) {
+                let available = if self.is_initialized() { 0 } else { N };
+                return Err(OutOfCapacity::new(layout, available));
+            }
+
+            let ptr = self.storage.as_ptr();
+            let init = construct_in(Slot::new_unchecked(ptr), constructor, layout);
+            self.meta = Some(init);
+            Ok(&mut *init.as_ptr())
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+
+impl<T: ?Sized, const N: usize, A> CapacityHint for &mut OnceSlot<T, N, A> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        !self.is_initialized() && Inline::<N, A>::new().can_fit(layout)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::utils::{randarr, randstr, DropCounter, OpqAny, OpqStrFut};
+    use crate::{from_closure, Dynify};
+
+    fn make_any<U: Any>(val: U) -> Inlined<dyn Any, 32> {
+        from_closure(|slot| slot.write(val) as &mut OpqAny).init(Inline::new())
+    }
+
+    #[test]
+    fn return_inlined() {
+        let inp = randstr(8..64);
+        let out = make_any(inp.clone());
+        let moved = [out];
+        assert_eq!(moved[0].downcast_ref::<String>(), Some(&inp));
+    }
+
+    #[test]
+    fn mutate_inlined() {
+        let out = make_any(Cell::new(1usize));
+        out.downcast_ref::<Cell<usize>>().unwrap().set(2);
+        let moved = std::convert::identity(out);
+        assert_eq!(moved.downcast_ref::<Cell<usize>>().unwrap().get(), 2);
+
+        let mut out = make_any(0usize);
+        *out.downcast_mut::<usize>().unwrap() = 3;
+        assert_eq!(out.downcast_ref::<usize>(), Some(&3));
+    }
+
+    #[test]
+    fn drop_inlined() {
+        drop(make_any(DropCounter));
+        assert_eq!(DropCounter::count(), 1);
+
+        let init = from_closure(|slot| slot.write((DropCounter, randstr(8..16))));
+        let out: Inlined<_, 64> = init.init(Inline::new());
+        let _ = out.into_inner();
+        assert_eq!(DropCounter::count(), 2);
+    }
+
+    #[test]
+    fn reject_oversized_object() {
+        let init = from_closure(|slot| slot.write(randarr::<33>()) as &mut OpqAny);
+        assert!(init.try_init(Inline::<32>::new()).is_err());
+
+        let init = from_closure(|slot| slot.write(0u128) as &mut OpqAny);
+        assert!(init.try_init(Inline::<32, u8>::new()).is_err());
+    }
+
+    #[pollster::test]
+    async fn inlined_future() {
+        let inp = randstr(8..64);
+        let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+        let out: Inlined<_, 64> = init.init(Inline::new());
+        let moved = std::convert::identity(out);
+        assert_eq!(moved.await, inp);
+    }
+
+    #[test]
+    fn init_once_slot() {
+        struct Lazy {
+            val: OnceSlot<dyn Any, 32>,
+        }
+
+        let mut lazy = Lazy {
+            val: OnceSlot::new(),
+        };
+        assert!(lazy.val.get().is_none());
+
+        let inp = randstr(8..64);
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        let out = init.init(&mut lazy.val);
+        assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+
+        let init = from_closure(|slot| slot.write(0usize) as &mut OpqAny);
+        let (_, err) = init.try_init(&mut lazy.val).unwrap_err();
+        assert_eq!(err.available(), 0);
+
+        let moved = [lazy];
+        assert_eq!(moved[0].val.downcast_ref::<String>(), Some(&inp));
+    }
+
+    #[test]
+    fn drop_once_slot() {
+        let mut slot = OnceSlot::<dyn Any, 16>::new();
+        from_closure(|slot| slot.write(DropCounter) as &mut OpqAny).init(&mut slot);
+        drop(slot);
+        assert_eq!(DropCounter::count(), 1);
+        drop(OnceSlot::<dyn Any, 16>::new());
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[test]
+    #[should_panic = "slot is not initialized"]
+    fn deref_empty_once_slot() {
+        let slot = OnceSlot::<dyn Any, 16>::new();
+        let _: &dyn Any = &*slot;
+    }
+}
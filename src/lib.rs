@@ -1,42 +1,128 @@
 #![doc = include_str!("lib.md") ]
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(dynify_ptr_metadata, feature(ptr_metadata))]
+#![cfg_attr(dynify_unsize, feature(unsize))]
 #![cfg_attr(not(test), no_std)]
 #![allow(unsafe_op_in_unsafe_fn)]
 #![deny(clippy::unsound_collection_transmute)]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 #[macro_use]
 mod utils;
+#[cfg(feature = "alloc")]
+mod arena;
 mod closure;
 mod constructor;
 mod container;
+pub mod container_core;
+mod erased;
+mod fallible;
+mod finally;
 mod function;
+#[cfg(target_has_atomic = "8")]
+mod global;
+mod guarded;
+#[cfg(feature = "alloc")]
+mod heap;
+#[cfg(feature = "pin-init")]
+mod init;
+mod inline;
+#[cfg(feature = "alloc")]
+mod mixed;
+mod pinned;
+#[cfg(feature = "alloc")]
+mod pool;
 mod receiver;
+mod ring;
+mod scratch;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "stats")]
+mod stats;
+mod value;
+mod zip;
 
 #[doc = include_str!("dynify.md") ]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 #[cfg(feature = "macros")]
 pub use dynify_macros::dynify;
 
+#[doc(inline)]
+#[cfg(feature = "allocator-api2")]
+pub use self::container::BoxedIn;
+#[doc(inline)]
+#[cfg(feature = "smallvec")]
+pub use self::container::{SmallBuffered, Spillable};
+#[doc(inline)]
+#[cfg(target_has_atomic = "8")]
+pub use self::global::StaticBuffer;
+#[doc(inline)]
+#[cfg(feature = "pin-init")]
+pub use self::init::{from_init, from_pin_init};
+#[doc(inline)]
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub use self::shared::Arced;
+#[doc(inline)]
+#[cfg(feature = "stats")]
+pub use self::stats::{ContainerStats, Tracked};
+#[doc(inline)]
+#[cfg(dynify_unsize)]
+pub use self::value::from_value_as;
 #[doc(inline)]
 #[cfg(feature = "alloc")]
-pub use self::container::Boxed;
+pub use self::{
+    arena::Arena,
+    container::{
+        hybrid, AllocError, Boxed, BoxedFuture, BoxedSendFuture, SpareCapacity, TryBoxed,
+        VecContainer,
+    },
+    heap::{Allocated, Heap},
+    mixed::Ptr,
+    pinned::PinVec,
+    pool::{Pool, Pooled},
+    scratch::Scratch,
+    shared::Rced,
+};
 #[doc(inline)]
 pub use self::{
-    closure::from_closure,
-    constructor::{Construct, Dynify, Opaque, PinConstruct, PinDynify, Slot},
-    container::{Buffered, Emplace, OutOfCapacity, PinEmplace},
+    closure::{from_closure, from_pin_closure, from_raw_parts},
+    constructor::{
+        from_option, CloneConstruct, Construct, DebugConstruct, Dynify, InitError, Opaque,
+        PinConstruct, PinDynify, Reusable, Slot,
+    },
+    container::{
+        AlignedBuf, AsBuffer, Bounded, Buffered, CapacityHint, Chain, DynAny, DynFuture,
+        DynSendFuture, Emplace, OutOfCapacity, PinEmplace, RawContainer, Reservation, Reset,
+        SizedFor, StackBuf, TryReserve, Uninit,
+    },
+    erased::{AnyConstructor, DynConstruct, DynConstructor, DynEmplace},
+    fallible::{from_try_closure, TryConstruct, TryDynify},
+    finally::Finally,
+    guarded::{guarded, ConstructHook},
+    inline::{Inline, Inlined, OnceSlot},
+    pinned::{PinBuffer, PinBuffered},
+    ring::{RingBuffer, RingBuffered},
+    scratch::{CellBuffer, ScratchBuffered},
+    value::{
+        from_bytes, from_clone, from_default, from_fn_array, from_iter, from_move, from_str,
+        from_value,
+    },
+    zip::{zip, Pair},
 };
 
 /// NON-PUBLIC API
 #[doc(hidden)]
 pub mod r#priv {
-    pub use crate::function::{from_bare_fn, from_method, Fn};
+    pub use crate::function::{
+        async_maker, buffer_for, from_async, from_bare_fn, from_method, layout_of, Fn,
+    };
     #[cfg(feature = "alloc")]
-    pub use crate::receiver::{ArcSelf, BoxSelf, RcSelf};
+    pub use crate::receiver::{ArcSelf, BoxSelf, RcSelf, RcWeakSelf, WeakSelf};
     pub use crate::receiver::{Receiver, RefMutSelf, RefSelf};
 
     pub type PinRefSelf<'a> = crate::receiver::Pin<RefSelf<'a>>;
@@ -0,0 +1,161 @@
+use alloc::boxed::Box;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+
+use crate::container::Buffered;
+use crate::pinned::PinBuffered;
+
+/// A pointer to objects stored either in a buffer or in a [`Box`].
+///
+/// It unifies the pointers yielded by buffer containers, [`PinBuffer`] and
+/// [`Boxed`], so that they can be mixed in a fallback chain, e.g., through
+/// [`Dynify::init2_mixed`] or [`PinDynify::pin_init_or_boxed`]. Like
+/// [`Buffered`], it pretends to own the value of `T`, which means it is
+/// `Unpin` only if `T` is `Unpin`.
+///
+/// [`Boxed`]: crate::Boxed
+/// [`PinBuffer`]: crate::PinBuffer
+/// [`Dynify::init2_mixed`]: crate::Dynify::init2_mixed
+/// [`PinDynify::pin_init_or_boxed`]: crate::PinDynify::pin_init_or_boxed
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub enum Ptr<'a, T: ?Sized> {
+    /// The object is stored in a buffer.
+    Buffered(Buffered<'a, T>),
+    /// The object is stored in a pinned buffer.
+    PinBuffered(PinBuffered<'a, T>),
+    /// The object is stored in a [`Box`].
+    Boxed(Box<T>),
+}
+impl<T: ?Sized> Ptr<'_, T> {
+    /// Returns `true` if the object is stored in a [`Box`].
+    pub fn is_boxed(&self) -> bool {
+        matches!(self, Self::Boxed(_))
+    }
+}
+
+impl<'a, T: ?Sized> From<Buffered<'a, T>> for Ptr<'a, T> {
+    fn from(value: Buffered<'a, T>) -> Self {
+        Self::Buffered(value)
+    }
+}
+impl<'a, T: ?Sized> From<PinBuffered<'a, T>> for Ptr<'a, T> {
+    fn from(value: PinBuffered<'a, T>) -> Self {
+        Self::PinBuffered(value)
+    }
+}
+impl<T: ?Sized> From<Box<T>> for Ptr<'_, T> {
+    fn from(value: Box<T>) -> Self {
+        Self::Boxed(value)
+    }
+}
+
+impl<T: ?Sized> Deref for Ptr<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Buffered(p) => p,
+            Self::PinBuffered(p) => p,
+            Self::Boxed(p) => p,
+        }
+    }
+}
+impl<T: ?Sized> DerefMut for Ptr<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Buffered(p) => p,
+            Self::PinBuffered(p) => p,
+            Self::Boxed(p) => p,
+        }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Ptr<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+impl<T: ?Sized + fmt::Display> fmt::Display for Ptr<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> core::future::Future for Ptr<'_, T>
+where
+    T: ?Sized + core::future::Future,
+{
+    type Output = T::Output;
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        // SAFETY: `Ptr` is `Unpin` only if `T` is `Unpin`, hence `T` is
+        // structurally pinned in all variants.
+        match unsafe { Pin::into_inner_unchecked(self) } {
+            Self::Buffered(p) => unsafe { Pin::new_unchecked(p) }.poll(cx),
+            Self::PinBuffered(p) => unsafe { Pin::new_unchecked(&mut **p) }.poll(cx),
+            Self::Boxed(p) => unsafe { Pin::new_unchecked(&mut **p) }.poll(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::mem::size_of_val;
+    use std::ptr;
+
+    use super::*;
+    use crate::utils::{newstk, randarr, randstr, OpqAny, OpqStrFut};
+    use crate::{from_closure, pin_buffer, Boxed, Dynify, PinDynify};
+
+    #[test]
+    fn mix_buffered_and_boxed() {
+        let mut stk = newstk::<8>();
+
+        let data = randarr::<4>();
+        let out = from_closure(|slot| slot.write(data) as &mut OpqAny).init2_mixed(&mut stk, Boxed);
+        assert!(!out.is_boxed());
+        assert_eq!(out.downcast_ref::<[u8; 4]>(), Some(&data));
+        drop(out);
+
+        let data = randarr::<16>();
+        let out: Ptr<_> =
+            from_closure(|slot| slot.write(data) as &mut OpqAny).init2_mixed(&mut stk, Boxed);
+        assert!(out.is_boxed());
+        assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&data));
+    }
+
+    #[pollster::test]
+    async fn poll_mixed_future() {
+        let mut stk = newstk::<64>();
+        let inp = randstr(8..64);
+
+        let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+        assert_eq!(init.init2_mixed(&mut stk, Boxed).await, inp);
+        let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+        assert_eq!(init.init2_mixed(&mut newstk::<0>(), Boxed).await, inp);
+    }
+
+    #[pollster::test]
+    async fn pin_init_or_boxed_ok() {
+        let mut stk = pin_buffer!(64);
+        let stk_range = {
+            let start = ptr::from_ref(&*stk).cast::<u8>();
+            start..start.wrapping_add(size_of_val(&*stk))
+        };
+        let inp = randstr(8..64);
+
+        let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+        let out = init.pin_init_or_boxed(stk.as_mut());
+        assert!(stk_range.contains(&ptr::from_ref(&*out).cast()));
+        assert_eq!(out.await, inp);
+
+        let data = randarr::<128>();
+        let init = from_closure(|slot| slot.write(async move { data }));
+        let out = init.pin_init_or_boxed(stk.as_mut());
+        assert!(!stk_range.contains(&ptr::from_ref(&*out).cast()));
+        assert_eq!(out.await, data);
+    }
+}
@@ -0,0 +1,448 @@
+use core::alloc::Layout;
+use core::fmt;
+use core::marker::{PhantomData, PhantomPinned};
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::constructor::Construct;
+use crate::container::{
+    buf_can_fit, buf_emplace, CapacityHint, Emplace, OutOfCapacity, PinEmplace,
+};
+use crate::container_core::construct_in;
+
+/// A fixed-size buffer used for pinned constructions.
+///
+/// Unlike plain buffers such as `[MaybeUninit<u8>; N]`, a pinned reference to
+/// this buffer can be used as a pinned container, which makes it possible to
+/// construct `!Unpin` objects without heap allocation. It can be pinned either
+/// on the stack with [`pin_buffer!`] or on the heap with [`Box::pin`].
+///
+/// # Leaking
+///
+/// The drop guarantee required by [`Pin`] forbids reusing the memory of a
+/// pinned object before its destructor runs. Therefore, if the returned pointer
+/// gets leaked through [`forget`], the buffer rejects subsequent constructions
+/// and aborts the process when it is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, pin_buffer, Fn, PinDynify};
+/// # use std::future::Future;
+/// # pollster::block_on(async {
+/// let constructor: Fn!(=> dyn Future<Output = i32>) = from_fn!(|| async { 777 });
+/// let mut stack = pin_buffer!(16);
+/// let ret = constructor.pin_init(stack.as_mut()).await;
+/// assert_eq!(ret, 777);
+/// # });
+/// ```
+///
+/// [`Box::pin`]: alloc::boxed::Box::pin
+/// [`forget`]: core::mem::forget
+/// [`pin_buffer!`]: crate::pin_buffer
+pub struct PinBuffer<const N: usize> {
+    occupied: bool,
+    buf: [MaybeUninit<u8>; N],
+    _pinned: PhantomPinned,
+}
+impl<const N: usize> PinBuffer<N> {
+    /// Creates a new buffer of `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            occupied: false,
+            buf: [MaybeUninit::uninit(); N],
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Returns the number of bytes this buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+impl<const N: usize> Default for PinBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> Drop for PinBuffer<N> {
+    fn drop(&mut self) {
+        if self.occupied {
+            crate::utils::abort("dropped a pinned buffer with a leaked object");
+        }
+    }
+}
+impl<const N: usize> fmt::Debug for PinBuffer<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PinBuffer")
+            .field("capacity", &N)
+            .field("occupied", &self.occupied)
+            .finish()
+    }
+}
+
+unsafe impl<'a, T: 'a + ?Sized, const N: usize> Emplace<T> for Pin<&'a mut PinBuffer<N>> {
+    type Ptr = PinBuffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            // SAFETY: The buffer is never moved out.
+            let this = Pin::get_unchecked_mut(self);
+            let layout = constructor.layout();
+            // The memory is still used by a leaked object.
+            if this.occupied {
+                return Err(OutOfCapacity::new(layout, 0));
+            }
+
+            let slot = buf_emplace(&mut this.buf, layout)?;
+            let init = construct_in(slot, constructor, layout);
+            this.occupied = true;
+            Ok(PinBuffered::new(init, &mut this.occupied))
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+// SAFETY: `PinBuffer` is `!Unpin` and never releases its memory until the
+// constructed object is dropped.
+unsafe impl<'a, T: 'a + ?Sized, const N: usize> PinEmplace<T> for Pin<&'a mut PinBuffer<N>> {}
+impl<const N: usize> CapacityHint for Pin<&mut PinBuffer<N>> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        !self.occupied && buf_can_fit(self.buf.as_ptr(), N, layout)
+    }
+}
+
+/// Creates a [`PinBuffer`] of the specified size pinned on the stack.
+///
+/// It expands to `core::pin::pin!(PinBuffer::<N>::new())`. See [`PinBuffer`]
+/// for an example.
+#[macro_export]
+macro_rules! pin_buffer {
+    ($n:expr $(,)?) => {
+        ::core::pin::pin!($crate::PinBuffer::<{ $n }>::new())
+    };
+}
+
+/// Constructs a future in a [`PinBuffer`] of the specified size and awaits it.
+///
+/// The future falls back to the heap if the buffer cannot fit it. This is a
+/// shorthand for creating a buffer with [`pin_buffer!`] and awaiting the
+/// future yielded by [`PinDynify::pin_init_or_boxed`], hence it must be used in
+/// async contexts.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, stack_await, Fn};
+/// # use std::future::Future;
+/// # pollster::block_on(async {
+/// let constructor: Fn!(=> dyn Future<Output = i32>) = from_fn!(|| async { 777 });
+/// assert_eq!(stack_await!(constructor, 16), 777);
+/// # });
+/// ```
+///
+/// [`PinDynify::pin_init_or_boxed`]: crate::PinDynify::pin_init_or_boxed
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[macro_export]
+macro_rules! stack_await {
+    ($init:expr, $n:expr $(,)?) => {{
+        let mut stack = $crate::pin_buffer!($n);
+        let fut = $crate::PinDynify::pin_init_or_boxed($init, stack.as_mut());
+        fut.await
+    }};
+}
+
+/// A pointer to objects stored in pinned buffers.
+///
+/// This pointer is yielded by pinned containers such as [`PinBuffer`]. It
+/// releases the occupied memory block once dropped.
+pub struct PinBuffered<'a, T: ?Sized> {
+    ptr: NonNull<T>,
+    occupied: &'a mut bool,
+    _marker: PhantomData<T>,
+}
+impl<'a, T: ?Sized> PinBuffered<'a, T> {
+    unsafe fn new(ptr: NonNull<T>, occupied: &'a mut bool) -> Self {
+        Self {
+            ptr,
+            occupied,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// SAFETY: We hold an exclusive reference to `T`, see `Buffered`.
+unsafe impl<T: ?Sized + Send> Send for PinBuffered<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for PinBuffered<'_, T> {}
+
+impl<T: ?Sized> Drop for PinBuffered<'_, T> {
+    fn drop(&mut self) {
+        // Release the memory even if the destructor panics, as the drop
+        // guarantee only requires the destructor to be invoked.
+        *self.occupied = false;
+        if core::mem::needs_drop::<T>() {
+            unsafe { self.ptr.drop_in_place() }
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for PinBuffered<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+impl<T: ?Sized> DerefMut for PinBuffered<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for PinBuffered<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod __alloc {
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::container::vec_emplace;
+
+    /// A growable heap buffer used for pinned constructions.
+    ///
+    /// The memory block owned by this buffer has a stable address, so it can be
+    /// reused across pinned constructions without allocating on each call. The
+    /// buffer only grows when no object is living in it.
+    ///
+    /// If the returned pointer gets leaked through [`forget`], the occupied
+    /// memory block is leaked as well, and a new one is allocated for
+    /// subsequent constructions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_fn, Fn, PinDynify, PinVec};
+    /// # use std::future::Future;
+    /// # pollster::block_on(async {
+    /// let mut heap = PinVec::new();
+    /// for i in 0..4 {
+    ///     let constructor: Fn!(i32 => dyn Future<Output = i32>) =
+    ///         from_fn!(|i| async move { i * 2 }, i);
+    ///     assert_eq!(constructor.pin_init(&mut heap).await, i * 2);
+    /// }
+    /// # });
+    /// ```
+    ///
+    /// [`forget`]: core::mem::forget
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[derive(Debug, Default)]
+    pub struct PinVec {
+        occupied: bool,
+        buf: Vec<MaybeUninit<u8>>,
+    }
+    impl PinVec {
+        /// Creates a new buffer without allocation.
+        pub const fn new() -> Self {
+            Self {
+                occupied: false,
+                buf: Vec::new(),
+            }
+        }
+
+        /// Creates a new buffer with at least the specified capacity.
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                occupied: false,
+                buf: Vec::with_capacity(capacity),
+            }
+        }
+
+        /// Returns the number of bytes this buffer can hold without
+        /// reallocating.
+        pub fn capacity(&self) -> usize {
+            self.buf.capacity()
+        }
+
+        /// Leaks the memory block if it is occupied by a leaked object.
+        fn release_leaked(&mut self) {
+            if self.occupied {
+                core::mem::forget(core::mem::take(&mut self.buf));
+                self.occupied = false;
+            }
+        }
+    }
+    impl Drop for PinVec {
+        fn drop(&mut self) {
+            self.release_leaked();
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a mut PinVec {
+        type Ptr = PinBuffered<'a, T>;
+        type Err = Infallible;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                self.release_leaked();
+
+                let layout = constructor.layout();
+                let slot = vec_emplace(&mut self.buf, layout);
+                let init = construct_in(slot, constructor, layout);
+                self.occupied = true;
+                Ok(PinBuffered::new(init, &mut self.occupied))
+            }
+        }
+    }
+    // SAFETY: The memory block of `PinVec` is never reallocated or released
+    // while it is occupied.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    unsafe impl<'a, T: 'a + ?Sized> PinEmplace<T> for &'a mut PinVec {}
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    impl CapacityHint for &mut PinVec {
+        fn can_fit(&self, _: Layout) -> bool {
+            true
+        }
+    }
+}
+#[cfg(feature = "alloc")]
+pub use __alloc::*;
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::marker::PhantomPinned;
+
+    use super::*;
+    use crate::utils::{randarr, randstr, DropCounter, OpqAny, OpqStrFut};
+    use crate::{from_closure, Dynify, Opaque, PinDynify};
+
+    #[pollster::test]
+    async fn pin_init_on_stack() {
+        let inp = randstr(8..64);
+        let mut stack = pin_buffer!(64);
+        let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+        assert_eq!(init.pin_init(stack.as_mut()).await, inp);
+    }
+
+    #[pollster::test]
+    async fn await_on_stack() {
+        let inp = randstr(8..64);
+        let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+        assert_eq!(stack_await!(init, 64), inp);
+
+        let data = randarr::<64>();
+        let init = from_closure(|slot| slot.write(async move { data }));
+        assert_eq!(stack_await!(init, 16), data);
+    }
+
+    #[test]
+    fn pin_init_not_unpin() {
+        let mut heap = Box::pin(PinBuffer::<16>::new());
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let _: Pin<PinBuffered<PhantomPinned>> = init.pin_init(heap.as_mut());
+    }
+
+    #[test]
+    fn reuse_pin_buffer() {
+        let mut stack = pin_buffer!(16);
+        for _ in 0..4 {
+            let inp = randarr::<16>();
+            let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+            let out = init.pin_init(stack.as_mut());
+            assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&inp));
+        }
+
+        let init = from_closure(|slot| slot.write(randarr::<17>()) as &mut OpqAny);
+        assert!(init.try_pin_init(stack.as_mut()).is_err());
+    }
+
+    #[test]
+    fn drop_pin_buffered() {
+        let mut stack = pin_buffer!(16);
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let out = init.init(stack.as_mut());
+        assert_eq!(DropCounter::count(), 0);
+        drop(out);
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[cfg_attr(miri, ignore)] // leaks memory intentionally
+    #[test]
+    fn reject_leaked_pin_buffer() {
+        let mut heap = Box::pin(PinBuffer::<16>::new());
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        std::mem::forget(init.pin_init(heap.as_mut()));
+
+        let init = from_closure(|slot| slot.write(123) as &mut OpqAny);
+        assert!(init.try_pin_init(heap.as_mut()).is_err());
+        // Dropping it would abort the process.
+        std::mem::forget(heap);
+    }
+
+    #[test]
+    fn reuse_pin_vec() {
+        let mut heap = PinVec::new();
+        for _ in 0..4 {
+            let inp = randstr(8..64);
+            let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+            let out = init.pin_init(&mut heap);
+            assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+        }
+
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let _: Pin<PinBuffered<PhantomPinned>> = init.pin_init(&mut heap);
+    }
+
+    #[test]
+    fn pin_vec_grows() {
+        let mut heap = PinVec::with_capacity(4);
+        let init = from_closure(|slot| slot.write(randarr::<64>()) as &mut OpqAny);
+        let _ = init.pin_init(&mut heap);
+        assert!(heap.capacity() >= 64);
+    }
+
+    #[cfg_attr(miri, ignore)] // leaks memory intentionally
+    #[test]
+    fn leak_pin_vec() {
+        let mut heap = PinVec::new();
+        let init = from_closure(|slot| slot.write((DropCounter, 0usize)) as &mut OpqAny);
+        let out = init.pin_init(&mut heap);
+        let leaked = std::ptr::from_ref::<dyn Any>(&*out);
+        std::mem::forget(out);
+
+        let init = from_closure(|slot| slot.write((DropCounter, 0usize)) as &mut OpqAny);
+        let out = init.pin_init(&mut heap);
+        assert_ne!(
+            std::ptr::from_ref::<dyn Any>(&*out) as *const (),
+            leaked as *const ()
+        );
+        drop(out);
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[test]
+    fn send_pin_buffered() {
+        fn ensure_send(_: impl Send) {}
+        let mut stack = pin_buffer!(16);
+        let init = from_closure(|slot| slot.write(123) as &mut Opaque<dyn Any + Send>);
+        ensure_send(init.pin_init(stack.as_mut()));
+        ensure_send(stack);
+    }
+}
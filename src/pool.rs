@@ -0,0 +1,279 @@
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::cell::UnsafeCell;
+use core::convert::Infallible;
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::constructor::Construct;
+use crate::container::{vec_emplace, Buffered, CapacityHint, Emplace, PinEmplace};
+use crate::container_core::construct_in;
+
+type Block = Vec<MaybeUninit<u8>>;
+
+/// A pool of reusable heap buffers.
+///
+/// Each construction takes a free buffer out of the pool, or allocates a new one
+/// if none is available, and the buffer is returned to the pool once the
+/// constructed object is dropped. Buffers grow to fit the largest object they
+/// have ever held, so repeated constructions of similar objects, such as the
+/// futures polled in each iteration of a loop, stop allocating after the first
+/// few rounds. Unlike [`Scratch`], a pool can hand out multiple live pointers at
+/// once.
+///
+/// Since each buffer is owned by the returned pointer until it is dropped, the
+/// pool can also be used as a pinned container. If the returned pointer gets
+/// leaked through [`forget`], its buffer is leaked as well.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, Dynify, Fn, Pool};
+/// # use std::future::Future;
+/// # pollster::block_on(async {
+/// let pool = Pool::new();
+/// for i in 0..4 {
+///     let constructor: Fn!(i32 => dyn Future<Output = i32>) =
+///         from_fn!(|i| async move { i * 2 }, i);
+///     assert_eq!(constructor.init(&pool).await, i * 2);
+/// }
+/// assert_eq!(pool.free_buffers(), 1);
+/// # });
+/// ```
+///
+/// [`Scratch`]: crate::Scratch
+/// [`forget`]: core::mem::forget
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Default)]
+pub struct Pool {
+    free: UnsafeCell<Vec<Block>>,
+}
+impl Pool {
+    /// Creates a new pool without allocation.
+    pub const fn new() -> Self {
+        Self {
+            free: UnsafeCell::new(Vec::new()),
+        }
+    }
+
+    /// Creates a new pool with `count` free buffers, each of which has at least
+    /// `size` bytes of capacity.
+    pub fn with_buffers(count: usize, size: usize) -> Self {
+        let free = (0..count).map(|_| Vec::with_capacity(size)).collect();
+        Self {
+            free: UnsafeCell::new(free),
+        }
+    }
+
+    /// Returns the number of buffers available for reuse.
+    pub fn free_buffers(&self) -> usize {
+        unsafe { (*self.free.get()).len() }
+    }
+
+    /// Releases all free buffers.
+    pub fn clear(&mut self) {
+        self.free.get_mut().clear();
+    }
+
+    /// Takes a free buffer out of this pool.
+    fn take(&self) -> Block {
+        // SAFETY: No references to the free list escape from this function, and
+        // it never runs user code while the list is borrowed.
+        unsafe { (*self.free.get()).pop().unwrap_or_default() }
+    }
+
+    /// Returns a buffer to this pool.
+    fn give_back(&self, block: Block) {
+        // SAFETY: See `Pool::take`.
+        unsafe { (*self.free.get()).push(block) }
+    }
+}
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("free_buffers", &self.free_buffers())
+            .finish()
+    }
+}
+
+unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a Pool {
+    type Ptr = Pooled<'a, T>;
+    type Err = Infallible;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let mut block = self.take();
+            let layout = constructor.layout();
+            let slot = vec_emplace(&mut block, layout);
+            // The buffer is simply released if `construct()` panics.
+            let init = construct_in(slot, constructor, layout);
+            Ok(Pooled::new(init, block, self))
+        }
+    }
+}
+// SAFETY: The buffer is owned by `Pooled` and never reused until the
+// constructed object is dropped.
+unsafe impl<'a, T: 'a + ?Sized> PinEmplace<T> for &'a Pool {}
+impl CapacityHint for &Pool {
+    fn can_fit(&self, _: Layout) -> bool {
+        true
+    }
+}
+
+/// A pointer to objects stored in [`Pool`].
+///
+/// It behaves the same as [`Buffered`] and returns the occupied buffer to the
+/// pool once dropped.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Pooled<'a, T: ?Sized> {
+    inner: ManuallyDrop<Buffered<'a, T>>,
+    block: ManuallyDrop<Block>,
+    pool: &'a Pool,
+}
+impl<'a, T: ?Sized> Pooled<'a, T> {
+    unsafe fn new(ptr: NonNull<T>, block: Block, pool: &'a Pool) -> Self {
+        Self {
+            inner: ManuallyDrop::new(Buffered::from_raw(ptr)),
+            block: ManuallyDrop::new(block),
+            pool,
+        }
+    }
+
+    /// Returns a pinned mutable reference to the inner value.
+    pub fn project(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe { self.map_unchecked_mut(|this| &mut **this.inner) }
+    }
+
+    /// Returns a pinned immutable reference to the inner value.
+    pub fn project_ref(self: Pin<&Self>) -> Pin<&T> {
+        unsafe { self.map_unchecked(|this| &**this.inner) }
+    }
+}
+
+// `Pooled` is not `Send` since the pool is not `Sync`. But it's fine to share
+// it, as the pool is never accessed through an immutable reference.
+unsafe impl<T: ?Sized + Sync> Sync for Pooled<'_, T> {}
+
+impl<T: ?Sized> Drop for Pooled<'_, T> {
+    fn drop(&mut self) {
+        // Return the buffer after the inner value is dropped, even if its
+        // destructor panics.
+        let block = unsafe { ManuallyDrop::take(&mut self.block) };
+        let pool = self.pool;
+        let _give_back = crate::utils::defer(move || pool.give_back(block));
+        unsafe { ManuallyDrop::drop(&mut self.inner) }
+    }
+}
+
+impl<T: ?Sized> Deref for Pooled<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+impl<T: ?Sized> DerefMut for Pooled<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Pooled<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> core::future::Future for Pooled<'_, T>
+where
+    T: ?Sized + core::future::Future,
+{
+    type Output = T::Output;
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        self.project().poll(cx)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::marker::PhantomPinned;
+
+    use super::*;
+    use crate::utils::{randarr, randstr, DropCounter, OpqAny, OpqStrFut};
+    use crate::{from_closure, Dynify, PinDynify};
+
+    #[pollster::test]
+    async fn reuse_buffers() {
+        let pool = Pool::new();
+        for _ in 0..4 {
+            let inp = randstr(8..64);
+            let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+            assert_eq!(init.init(&pool).await, inp);
+            assert_eq!(pool.free_buffers(), 1);
+        }
+    }
+
+    #[test]
+    fn hold_multiple_objects() {
+        let pool = Pool::with_buffers(2, 64);
+        let inps = (0..4).map(|_| randstr(8..64)).collect::<Vec<_>>();
+        let outs = inps
+            .iter()
+            .map(|inp| from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny).init(&pool))
+            .collect::<Vec<Pooled<dyn Any>>>();
+        assert_eq!(pool.free_buffers(), 0);
+        for (inp, out) in inps.iter().zip(&outs) {
+            assert_eq!(out.downcast_ref::<String>(), Some(inp));
+        }
+        drop(outs);
+        assert_eq!(pool.free_buffers(), 4);
+    }
+
+    #[test]
+    fn grow_buffers() {
+        let pool = Pool::with_buffers(1, 8);
+        let inp = randarr::<256>();
+        let out = from_closure(|slot| slot.write(inp) as &mut OpqAny).init(&pool);
+        assert_eq!(out.downcast_ref::<[u8; 256]>(), Some(&inp));
+        drop(out);
+        assert_eq!(pool.free_buffers(), 1);
+        let mut pool = pool;
+        pool.clear();
+        assert_eq!(pool.free_buffers(), 0);
+    }
+
+    #[test]
+    fn return_buffer_on_panic() {
+        struct PanicOnDrop;
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                panic!("just panic");
+            }
+        }
+
+        let pool = Pool::new();
+        let init = from_closure(|slot| slot.write((DropCounter, PanicOnDrop)));
+        let out = init.init(&pool);
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(out)));
+        assert!(res.is_err());
+        assert_eq!(DropCounter::count(), 1);
+        assert_eq!(pool.free_buffers(), 1);
+    }
+
+    #[test]
+    fn pin_init_pool() {
+        let pool = Pool::new();
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let _: Pin<Pooled<PhantomPinned>> = init.pin_init(&pool);
+    }
+}
@@ -13,7 +13,7 @@ use crate::utils::{Void, VoidPtr};
 /// The implementor must adhere the documented contracts of each method.
 ///
 /// [`Fn`]: crate::function::Fn
-pub unsafe trait Receiver: core::ops::Deref {
+pub unsafe trait Receiver {
     /// The sealed type of this receiver.
     type Sealed;
 
@@ -34,7 +34,7 @@ pub unsafe trait Receiver: core::ops::Deref {
 
 /// The sealed type of `Pin<T>`.
 pub struct Pin<T>(T);
-unsafe impl<T: Receiver> Receiver for core::pin::Pin<T> {
+unsafe impl<T: Receiver + core::ops::Deref> Receiver for core::pin::Pin<T> {
     type Sealed = Pin<T::Sealed>;
     fn seal(self) -> Self::Sealed {
         unsafe {
@@ -74,8 +74,8 @@ unsafe impl<'a, T> Receiver for &'a mut T {
 #[cfg(feature = "alloc")]
 mod __alloc {
     use alloc::boxed::Box;
-    use alloc::rc::Rc;
-    use alloc::sync::Arc;
+    use alloc::rc::{self, Rc};
+    use alloc::sync::{self, Arc};
 
     use super::*;
 
@@ -158,6 +158,48 @@ mod __alloc {
             Arc::from_raw(data.cast().as_ptr())
         }
     }
+
+    /// The sealed type of `rc::Weak<Self>`.
+    pub struct RcWeakSelf(AllocReceiver);
+    unsafe impl<T> Receiver for rc::Weak<T> {
+        type Sealed = RcWeakSelf;
+        fn seal(self) -> Self::Sealed {
+            unsafe fn drop_fn<T>(data: VoidPtr) {
+                drop(rc::Weak::from_raw(data.cast::<T>().as_ptr()));
+            }
+            unsafe {
+                RcWeakSelf(AllocReceiver {
+                    data: NonNull::new_unchecked(rc::Weak::into_raw(self).cast_mut()).cast(),
+                    drop_fn: drop_fn::<T>,
+                })
+            }
+        }
+        unsafe fn unseal(sealed: Self::Sealed) -> Self {
+            let data = sealed.0.into_raw();
+            rc::Weak::from_raw(data.cast().as_ptr())
+        }
+    }
+
+    /// The sealed type of `Weak<Self>`.
+    pub struct WeakSelf(AllocReceiver);
+    unsafe impl<T> Receiver for sync::Weak<T> {
+        type Sealed = WeakSelf;
+        fn seal(self) -> Self::Sealed {
+            unsafe fn drop_fn<T>(data: VoidPtr) {
+                drop(sync::Weak::from_raw(data.cast::<T>().as_ptr()));
+            }
+            unsafe {
+                WeakSelf(AllocReceiver {
+                    data: NonNull::new_unchecked(sync::Weak::into_raw(self).cast_mut()).cast(),
+                    drop_fn: drop_fn::<T>,
+                })
+            }
+        }
+        unsafe fn unseal(sealed: Self::Sealed) -> Self {
+            let data = sealed.0.into_raw();
+            sync::Weak::from_raw(data.cast().as_ptr())
+        }
+    }
 }
 #[cfg(feature = "alloc")]
 pub use __alloc::*;
@@ -165,8 +207,8 @@ pub use __alloc::*;
 #[cfg(test)]
 mod tests {
     use std::pin::Pin;
-    use std::rc::Rc;
-    use std::sync::Arc;
+    use std::rc::{self, Rc};
+    use std::sync::{self, Arc};
 
     use rstest::rstest;
 
@@ -184,7 +226,7 @@ mod tests {
     #[case(Pin::new(Box::new(FakeSelf(6))))]
     fn unsealed_ptr_matches_original<R>(#[case] orig: R)
     where
-        R: Receiver<Target = FakeSelf>,
+        R: Receiver + core::ops::Deref<Target = FakeSelf>,
     {
         let orig_addr = std::ptr::from_ref(&*orig);
         let orig_val = orig.0;
@@ -206,4 +248,31 @@ mod tests {
         drop(recv);
         assert_eq!(DropCounter::count(), 1);
     }
+
+    #[test]
+    fn unsealed_weak_matches_original() {
+        let rc = Rc::new(FakeSelf(7));
+        let weak: rc::Weak<FakeSelf> = unsafe { Receiver::unseal(Rc::downgrade(&rc).seal()) };
+        assert!(weak.ptr_eq(&Rc::downgrade(&rc)));
+        assert_eq!(Rc::weak_count(&rc), 1);
+
+        let arc = Arc::new(FakeSelf(8));
+        let weak: sync::Weak<FakeSelf> = unsafe { Receiver::unseal(Arc::downgrade(&arc).seal()) };
+        assert_eq!(weak.upgrade().map(|this| this.0), Some(8));
+    }
+
+    #[test]
+    fn sealed_weak_drop_works() {
+        let rc = Rc::new(FakeSelf(9));
+        drop(Rc::downgrade(&rc).seal());
+        assert_eq!(Rc::weak_count(&rc), 0);
+
+        let arc = Arc::new(FakeSelf(10));
+        drop(Arc::downgrade(&arc).seal());
+        assert_eq!(Arc::weak_count(&arc), 0);
+
+        let dangling = sync::Weak::<FakeSelf>::new();
+        let weak: sync::Weak<FakeSelf> = unsafe { Receiver::unseal(dangling.seal()) };
+        assert!(weak.upgrade().is_none());
+    }
 }
@@ -0,0 +1,474 @@
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, Slot};
+use crate::container::{dangling_slot, Buffered, CapacityHint, Emplace, OutOfCapacity};
+use crate::container_core::construct_in;
+
+/// The size of region headers, which is also the alignment of regions.
+const HEADER: usize = core::mem::size_of::<usize>();
+/// The flag set in a region header once the region is no longer used.
+const FREED: usize = 1;
+
+/// A fixed-size buffer that holds multiple live objects and reclaims their
+/// memory in FIFO order.
+///
+/// Objects are placed one after another, wrapping around to the start of the
+/// buffer when they reach its end. Once dropped, the memory of an object is
+/// reclaimed together with that of all older objects, hence it suits pipelines
+/// that keep several constructions alive and complete them roughly in order.
+/// Each object takes a few extra bytes for bookkeeping, and constructions fail
+/// with [`OutOfCapacity`] if the free memory cannot fit the object.
+///
+/// It is used through a shared reference, so it can be stored alongside the
+/// code that uses it. If a pointer gets leaked through [`forget`], its memory
+/// and that of all newer objects will never be reclaimed until [`reclaim`] is
+/// called.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, Dynify, Fn, RingBuffer};
+/// # use std::future::Future;
+/// fn double(n: i32) -> Fn!(i32 => dyn Future<Output = i32>) {
+///     from_fn!(|n| async move { n * 2 }, n)
+/// }
+///
+/// # pollster::block_on(async {
+/// let ring = RingBuffer::<256>::new();
+/// let mut pending = std::collections::VecDeque::new();
+/// for n in 0..16 {
+///     if pending.len() == 4 {
+///         assert_eq!(pending.pop_front().unwrap().await, (n - 4) * 2);
+///     }
+///     pending.push_back(double(n).init(&ring));
+/// }
+/// # });
+/// ```
+///
+/// [`forget`]: core::mem::forget
+/// [`reclaim`]: Self::reclaim
+#[repr(C)]
+pub struct RingBuffer<const N: usize> {
+    _align: [usize; 0],
+    buf: UnsafeCell<[MaybeUninit<u8>; N]>,
+    ring: Ring,
+}
+impl<const N: usize> RingBuffer<N> {
+    /// Creates a new buffer of `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            _align: [],
+            buf: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            ring: Ring {
+                head: Cell::new(0),
+                tail: Cell::new(0),
+                regions: Cell::new(0),
+                capacity: N / HEADER * HEADER,
+            },
+        }
+    }
+
+    /// Returns the number of bytes this buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns whether no objects are living in this buffer.
+    pub fn is_empty(&self) -> bool {
+        self.ring.regions.get() == 0
+    }
+
+    /// Reclaims the memory blocks occupied by leaked objects.
+    ///
+    /// An exclusive reference ensures no objects live in this buffer, so all
+    /// occupied memory blocks must have been leaked, and they are safe to
+    /// reuse.
+    pub fn reclaim(&mut self) {
+        self.ring.reset();
+    }
+
+    fn base(&self) -> NonNull<u8> {
+        unsafe { NonNull::new_unchecked(self.buf.get().cast()) }
+    }
+}
+impl<const N: usize> Default for RingBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> fmt::Debug for RingBuffer<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingBuffer")
+            .field("capacity", &N)
+            .field("empty", &self.is_empty())
+            .finish()
+    }
+}
+
+unsafe impl<'a, T: 'a + ?Sized, const N: usize> Emplace<T> for &'a RingBuffer<N> {
+    type Ptr = RingBuffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let layout = constructor.layout();
+            if layout.size() == 0 {
+                let slot = dangling_slot(layout);
+                let init = construct_in(slot, constructor, layout);
+                return Ok(RingBuffered::new(init, None));
+            }
+
+            let base = self.base();
+            let plan = self
+                .ring
+                .find(base, layout)
+                .ok_or_else(|| OutOfCapacity::new(layout, self.ring.available()))?;
+            // The region is reserved before construction, so it's fine if
+            // `construct()` places other objects in this buffer.
+            let region = self.ring.reserve(base, plan);
+            let release_on_panic = crate::utils::defer(|| region.release());
+
+            let ptr = base.add(plan.object);
+            let init = construct_in(Slot::new_unchecked(ptr), constructor, layout);
+
+            core::mem::forget(release_on_panic);
+            Ok(RingBuffered::new(init, Some(region)))
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl<const N: usize> CapacityHint for &RingBuffer<N> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        layout.size() == 0 || self.ring.find(self.base(), layout).is_some()
+    }
+}
+
+/// The bookkeeping states of [`RingBuffer`].
+///
+/// Each region starts with a header holding the offset of the next region,
+/// along with the [`FREED`] flag. Regions between `head` and `tail` are in use,
+/// unless they are freed but not yet reclaimed.
+struct Ring {
+    head: Cell<usize>,
+    tail: Cell<usize>,
+    regions: Cell<usize>,
+    capacity: usize,
+}
+
+/// Where to place a new region.
+#[derive(Clone, Copy)]
+struct Plan {
+    wrap: bool,
+    start: usize,
+    object: usize,
+    end: usize,
+}
+
+impl Ring {
+    fn reset(&self) {
+        self.head.set(0);
+        self.tail.set(0);
+        self.regions.set(0);
+    }
+
+    /// Returns the number of contiguous free bytes, used for error reports.
+    fn available(&self) -> usize {
+        let (head, tail) = (self.head.get(), self.tail.get());
+        if self.regions.get() == 0 {
+            self.capacity
+        } else if tail > head {
+            usize::max(self.capacity - tail, head)
+        } else {
+            head - tail
+        }
+    }
+
+    fn find(&self, base: NonNull<u8>, layout: Layout) -> Option<Plan> {
+        let (head, tail) = if self.regions.get() == 0 {
+            (0, 0)
+        } else {
+            (self.head.get(), self.tail.get())
+        };
+        let place = |start: usize, limit: usize| {
+            let object = start + HEADER;
+            let addr = base.as_ptr().wrapping_add(object);
+            let object = object.checked_add(addr.align_offset(layout.align()))?;
+            let end = object
+                .checked_add(layout.size())?
+                .checked_next_multiple_of(HEADER)?;
+            (end <= limit).then_some(Plan {
+                wrap: false,
+                start,
+                object,
+                end,
+            })
+        };
+
+        if self.regions.get() == 0 || tail > head {
+            place(tail, self.capacity).or_else(|| {
+                // Objects never wrap around if the buffer is empty.
+                if self.regions.get() == 0 {
+                    return None;
+                }
+                place(0, head).map(|plan| Plan { wrap: true, ..plan })
+            })
+        } else if tail < head {
+            place(tail, head)
+        } else {
+            None
+        }
+    }
+
+    unsafe fn reserve(&self, base: NonNull<u8>, plan: Plan) -> Region<'_> {
+        if self.regions.get() == 0 {
+            self.reset();
+        }
+        let tail = self.tail.get();
+        if plan.wrap && tail < self.capacity {
+            // Mark the remaining bytes as a freed region to skip them.
+            write_header(base, tail, FREED);
+            self.regions.set(self.regions.get() + 1);
+        }
+        write_header(base, plan.start, plan.end);
+        self.regions.set(self.regions.get() + 1);
+        self.tail.set(if plan.end == self.capacity {
+            0
+        } else {
+            plan.end
+        });
+        Region {
+            ring: self,
+            base,
+            header: plan.start,
+        }
+    }
+}
+
+unsafe fn read_header(base: NonNull<u8>, offset: usize) -> usize {
+    base.as_ptr().add(offset).cast::<usize>().read()
+}
+unsafe fn write_header(base: NonNull<u8>, offset: usize, header: usize) {
+    base.as_ptr().add(offset).cast::<usize>().write(header)
+}
+
+/// A region reserved in [`Ring`].
+struct Region<'a> {
+    ring: &'a Ring,
+    base: NonNull<u8>,
+    header: usize,
+}
+impl Region<'_> {
+    /// Marks this region as freed, and reclaims all freed regions from the
+    /// oldest one.
+    fn release(&self) {
+        let Self { ring, base, header } = *self;
+        unsafe {
+            write_header(base, header, read_header(base, header) | FREED);
+            while ring.regions.get() > 0 {
+                let head = read_header(base, ring.head.get());
+                if head & FREED == 0 {
+                    break;
+                }
+                let next = head & !FREED;
+                ring.head.set(if next == ring.capacity { 0 } else { next });
+                ring.regions.set(ring.regions.get() - 1);
+            }
+        }
+    }
+}
+
+/// A pointer to objects stored in [`RingBuffer`].
+///
+/// It behaves the same as [`Buffered`] and releases the occupied memory once
+/// dropped.
+pub struct RingBuffered<'a, T: ?Sized> {
+    inner: ManuallyDrop<Buffered<'a, T>>,
+    region: Option<Region<'a>>,
+}
+impl<'a, T: ?Sized> RingBuffered<'a, T> {
+    unsafe fn new(ptr: NonNull<T>, region: Option<Region<'a>>) -> Self {
+        Self {
+            inner: ManuallyDrop::new(Buffered::from_raw(ptr)),
+            region,
+        }
+    }
+
+    /// Returns a pinned mutable reference to the inner value.
+    pub fn project(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe { self.map_unchecked_mut(|this| &mut **this.inner) }
+    }
+
+    /// Returns a pinned immutable reference to the inner value.
+    pub fn project_ref(self: Pin<&Self>) -> Pin<&T> {
+        unsafe { self.map_unchecked(|this| &**this.inner) }
+    }
+}
+
+// `RingBuffered` is not `Send` since the bookkeeping states are shared with the
+// buffer, which is not `Sync`. But it's fine to share it, as the states are
+// never accessed through an immutable reference.
+unsafe impl<T: ?Sized + Sync> Sync for RingBuffered<'_, T> {}
+
+impl<T: ?Sized> Drop for RingBuffered<'_, T> {
+    fn drop(&mut self) {
+        // Release the region after the inner value is dropped, even if its
+        // destructor panics.
+        let region = self.region.take();
+        let _release = crate::utils::defer(|| {
+            if let Some(region) = region {
+                region.release();
+            }
+        });
+        unsafe { ManuallyDrop::drop(&mut self.inner) }
+    }
+}
+
+impl<T: ?Sized> Deref for RingBuffered<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+impl<T: ?Sized> DerefMut for RingBuffered<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RingBuffered<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> core::future::Future for RingBuffered<'_, T>
+where
+    T: ?Sized + core::future::Future,
+{
+    type Output = T::Output;
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        self.project().poll(cx)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::utils::{randarr, DropCounter, OpqAny};
+    use crate::{from_closure, Dynify};
+
+    fn push<const M: usize>(ring: &RingBuffer<M>, inp: [u8; 16]) -> RingBuffered<'_, dyn Any> {
+        let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+        init.init(ring)
+    }
+
+    #[test]
+    fn hold_multiple_objects() {
+        let ring = RingBuffer::<96>::new();
+        let inps = [
+            randarr::<16>(),
+            randarr::<16>(),
+            randarr::<16>(),
+            randarr::<16>(),
+        ];
+        let outs = inps.map(|inp| push(&ring, inp));
+        for (out, inp) in outs.iter().zip(&inps) {
+            assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(inp));
+        }
+
+        let init = from_closure(|slot| slot.write(randarr::<16>()) as &mut OpqAny);
+        assert!(init.try_init(&ring).is_err());
+        drop(outs);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn reclaim_in_order() {
+        let ring = RingBuffer::<72>::new();
+        let first = push(&ring, randarr());
+        let second = push(&ring, randarr());
+        let third = push(&ring, randarr());
+
+        // The second region is freed but not reclaimed.
+        drop(second);
+        let init = from_closure(|slot| slot.write(randarr::<16>()) as &mut OpqAny);
+        let (init, _) = init.try_init(&ring).unwrap_err();
+        // Both regions are reclaimed after the first one is freed.
+        drop(first);
+        let wrapped = init.init(&ring);
+        let _ = push(&ring, randarr());
+        drop((third, wrapped));
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn stream_objects() {
+        // The remaining 8 bytes are skipped when objects wrap around.
+        let ring = RingBuffer::<80>::new();
+        let mut pending = VecDeque::new();
+        for _ in 0..64 {
+            if pending.len() == 2 {
+                let (out, inp): (RingBuffered<dyn Any>, _) = pending.pop_front().unwrap();
+                assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&inp));
+            }
+            let inp = randarr::<16>();
+            pending.push_back((push(&ring, inp), inp));
+        }
+        drop(pending);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn drop_ring_buffered() {
+        let ring = RingBuffer::<64>::new();
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        drop(init.init(&ring));
+        assert_eq!(DropCounter::count(), 1);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn release_ring_buffer_on_panic() {
+        let ring = RingBuffer::<64>::new();
+        let init = from_closure::<usize, usize, _>(|_| panic!("just panic"));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| init.init(&ring)));
+        assert!(res.is_err());
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn reclaim_leaked_ring_buffer() {
+        let mut ring = RingBuffer::<32>::new();
+        std::mem::forget(push(&ring, randarr()));
+        assert!(!ring.is_empty());
+
+        ring.reclaim();
+        assert!(ring.is_empty());
+        let _ = push(&ring, randarr());
+    }
+
+    #[test]
+    fn never_fail_on_zst() {
+        let ring = RingBuffer::<0>::new();
+        let init = from_closure(|slot| slot.write(()) as &mut OpqAny);
+        assert!(init.try_init(&ring).is_ok());
+    }
+}
@@ -0,0 +1,487 @@
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::constructor::Construct;
+use crate::container::{buf_can_fit, buf_emplace, Buffered, CapacityHint, Emplace, OutOfCapacity};
+use crate::container_core::construct_in;
+
+#[cfg(feature = "alloc")]
+mod __alloc {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::container::{vec_emplace, PinEmplace};
+
+    /// A reusable heap buffer with occupancy tracking.
+    ///
+    /// Unlike `&mut Vec<MaybeUninit<u8>>`, this container is used through a
+    /// shared reference, and the exclusiveness of the memory block is checked
+    /// at runtime: it holds at most one live object at a time, and rejects
+    /// constructions with [`OutOfCapacity`] while occupied. This makes it
+    /// convenient to store the buffer alongside the code that uses it.
+    ///
+    /// The memory block is never reallocated while occupied, therefore it can
+    /// also be used as a pinned container. If the returned pointer gets leaked
+    /// through [`forget`], the buffer remains occupied until [`reclaim`] is
+    /// called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use dynify::{from_fn, Dynify, Fn, Scratch};
+    /// # use std::future::Future;
+    /// struct Worker {
+    ///     scratch: Scratch,
+    /// }
+    /// impl Worker {
+    ///     async fn run(&self, task: Fn!(=> dyn '_ + Future<Output = i32>)) -> i32 {
+    ///         task.init(&self.scratch).await
+    ///     }
+    /// }
+    /// # pollster::block_on(async {
+    /// let worker = Worker {
+    ///     scratch: Scratch::new(),
+    /// };
+    /// assert_eq!(worker.run(from_fn!(|| async { 7 })).await, 7);
+    /// assert_eq!(worker.run(from_fn!(|| async { 77 })).await, 77);
+    /// # });
+    /// ```
+    ///
+    /// [`forget`]: core::mem::forget
+    /// [`reclaim`]: Self::reclaim
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[derive(Default)]
+    pub struct Scratch {
+        occupied: Cell<bool>,
+        buf: UnsafeCell<Vec<MaybeUninit<u8>>>,
+    }
+    impl Scratch {
+        /// Creates a new buffer without allocation.
+        pub const fn new() -> Self {
+            Self {
+                occupied: Cell::new(false),
+                buf: UnsafeCell::new(Vec::new()),
+            }
+        }
+
+        /// Creates a new buffer with at least the specified capacity.
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                occupied: Cell::new(false),
+                buf: UnsafeCell::new(Vec::with_capacity(capacity)),
+            }
+        }
+
+        /// Returns the number of bytes this buffer can hold without
+        /// reallocating.
+        pub fn capacity(&self) -> usize {
+            unsafe { (*self.buf.get()).capacity() }
+        }
+
+        /// Returns whether an object is living in this buffer.
+        pub fn is_occupied(&self) -> bool {
+            self.occupied.get()
+        }
+
+        /// Reclaims the memory block occupied by a leaked object.
+        ///
+        /// An exclusive reference ensures no objects live in this buffer, so
+        /// the occupied memory block must have been leaked. In this case, it
+        /// gets leaked as well to uphold the pinning requirements, and a new
+        /// one will be allocated for subsequent constructions.
+        pub fn reclaim(&mut self) {
+            if self.occupied.replace(false) {
+                core::mem::forget(core::mem::take(self.buf.get_mut()));
+            }
+        }
+    }
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            self.reclaim();
+        }
+    }
+    impl fmt::Debug for Scratch {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Scratch")
+                .field("capacity", &self.capacity())
+                .field("occupied", &self.is_occupied())
+                .finish()
+        }
+    }
+
+    unsafe impl<'a, T: 'a + ?Sized> Emplace<T> for &'a Scratch {
+        type Ptr = ScratchBuffered<'a, T>;
+        type Err = OutOfCapacity;
+
+        fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+        where
+            C: Construct<Object = T>,
+        {
+            unsafe {
+                let layout = constructor.layout();
+                if self.occupied.replace(true) {
+                    return Err(OutOfCapacity::new(layout, 0));
+                }
+                // Release the buffer if `construct()` panics. It is occupied
+                // during construction in case `construct()` attempts to use it
+                // as well.
+                let release_on_panic = crate::utils::defer(|| self.occupied.set(false));
+
+                let slot = vec_emplace(&mut *self.buf.get(), layout);
+                let init = construct_in(slot, constructor, layout);
+
+                core::mem::forget(release_on_panic);
+                Ok(ScratchBuffered::new(init, &self.occupied))
+            }
+        }
+        fn available(err: &Self::Err) -> Option<usize> {
+            Some(err.available())
+        }
+    }
+    // SAFETY: The memory block of `Scratch` is never reallocated or released
+    // while it is occupied.
+    unsafe impl<'a, T: 'a + ?Sized> PinEmplace<T> for &'a Scratch {}
+    impl CapacityHint for &Scratch {
+        fn can_fit(&self, _: Layout) -> bool {
+            !self.is_occupied()
+        }
+    }
+}
+#[cfg(feature = "alloc")]
+pub use __alloc::*;
+
+/// A fixed-size buffer with occupancy tracking.
+///
+/// It works the same as [`Scratch`], but stores `N` bytes aligned to `A` inline
+/// rather than on the heap, so it is available without the `alloc` feature.
+/// It holds at most one live object at a time, and rejects constructions with
+/// [`OutOfCapacity`] while occupied or if the object does not fit.
+///
+/// Since the buffer may be moved once the returned pointer gets leaked through
+/// [`forget`], it cannot be used as a pinned container.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_fn, CellBuffer, Dynify, Fn};
+/// # use std::future::Future;
+/// struct Worker {
+///     buf: CellBuffer<64>,
+/// }
+/// impl Worker {
+///     async fn run(&self, task: Fn!(=> dyn '_ + Future<Output = i32>)) -> i32 {
+///         task.init(&self.buf).await
+///     }
+/// }
+/// # pollster::block_on(async {
+/// let worker = Worker {
+///     buf: CellBuffer::new(),
+/// };
+/// assert_eq!(worker.run(from_fn!(|| async { 7 })).await, 7);
+/// assert_eq!(worker.run(from_fn!(|| async { 77 })).await, 77);
+/// # });
+/// ```
+///
+/// [`Scratch`]: crate::Scratch
+/// [`forget`]: core::mem::forget
+#[repr(C)]
+pub struct CellBuffer<const N: usize, A = usize> {
+    _align: [A; 0],
+    buf: UnsafeCell<[MaybeUninit<u8>; N]>,
+    occupied: Cell<bool>,
+}
+impl<const N: usize, A> CellBuffer<N, A> {
+    /// Creates a new buffer of `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            _align: [],
+            buf: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            occupied: Cell::new(false),
+        }
+    }
+
+    /// Returns the number of bytes this buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns whether an object is living in this buffer.
+    pub fn is_occupied(&self) -> bool {
+        self.occupied.get()
+    }
+
+    /// Reclaims the memory block occupied by a leaked object.
+    ///
+    /// An exclusive reference ensures no objects live in this buffer, so the
+    /// occupied memory block must have been leaked, and it is safe to reuse.
+    pub fn reclaim(&mut self) {
+        self.occupied.set(false);
+    }
+}
+impl<const N: usize, A> Default for CellBuffer<N, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize, A> fmt::Debug for CellBuffer<N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellBuffer")
+            .field("capacity", &N)
+            .field("align", &core::mem::align_of::<A>())
+            .field("occupied", &self.is_occupied())
+            .finish()
+    }
+}
+
+unsafe impl<'a, T: 'a + ?Sized, const N: usize, A> Emplace<T> for &'a CellBuffer<N, A> {
+    type Ptr = ScratchBuffered<'a, T>;
+    type Err = OutOfCapacity;
+
+    fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+    where
+        C: Construct<Object = T>,
+    {
+        unsafe {
+            let layout = constructor.layout();
+            if self.occupied.replace(true) {
+                return Err(OutOfCapacity::new(layout, 0));
+            }
+            // Release the buffer if the construction fails or panics.
+            let release = crate::utils::defer(|| self.occupied.set(false));
+
+            let slot = buf_emplace(&mut *self.buf.get(), layout)?;
+            let init = construct_in(slot, constructor, layout);
+
+            core::mem::forget(release);
+            Ok(ScratchBuffered::new(init, &self.occupied))
+        }
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        Some(err.available())
+    }
+}
+impl<const N: usize, A> CapacityHint for &CellBuffer<N, A> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        !self.is_occupied() && buf_can_fit(self.buf.get().cast(), N, layout)
+    }
+}
+
+/// A pointer to objects stored in [`Scratch`] or [`CellBuffer`].
+///
+/// It behaves the same as [`Buffered`] and releases the occupied buffer once
+/// dropped.
+///
+/// [`Scratch`]: crate::Scratch
+pub struct ScratchBuffered<'a, T: ?Sized> {
+    inner: ManuallyDrop<Buffered<'a, T>>,
+    occupied: &'a Cell<bool>,
+}
+impl<'a, T: ?Sized> ScratchBuffered<'a, T> {
+    unsafe fn new(ptr: NonNull<T>, occupied: &'a Cell<bool>) -> Self {
+        Self {
+            inner: ManuallyDrop::new(Buffered::from_raw(ptr)),
+            occupied,
+        }
+    }
+
+    /// Returns a pinned mutable reference to the inner value.
+    pub fn project(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe { self.map_unchecked_mut(|this| &mut **this.inner) }
+    }
+
+    /// Returns a pinned immutable reference to the inner value.
+    pub fn project_ref(self: Pin<&Self>) -> Pin<&T> {
+        unsafe { self.map_unchecked(|this| &**this.inner) }
+    }
+}
+
+// `ScratchBuffered` is not `Send` since the occupancy flag is shared with the
+// buffer, which is not `Sync`. But it's fine to share it, as the flag is never
+// accessed through an immutable reference.
+unsafe impl<T: ?Sized + Sync> Sync for ScratchBuffered<'_, T> {}
+
+impl<T: ?Sized> Drop for ScratchBuffered<'_, T> {
+    fn drop(&mut self) {
+        // Release the buffer after the inner value is dropped, even if its
+        // destructor panics. It is not released earlier, in case the destructor
+        // attempts to reuse the buffer.
+        let occupied = self.occupied;
+        let _release = crate::utils::defer(|| occupied.set(false));
+        unsafe { ManuallyDrop::drop(&mut self.inner) }
+    }
+}
+
+impl<T: ?Sized> Deref for ScratchBuffered<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+impl<T: ?Sized> DerefMut for ScratchBuffered<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ScratchBuffered<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> core::future::Future for ScratchBuffered<'_, T>
+where
+    T: ?Sized + core::future::Future,
+{
+    type Output = T::Output;
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        self.project().poll(cx)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::marker::PhantomPinned;
+
+    use super::*;
+    use crate::utils::{randarr, randstr, DropCounter, OpqAny, OpqStrFut};
+    use crate::{from_closure, Dynify, PinDynify};
+
+    #[pollster::test]
+    async fn reuse_scratch() {
+        let scratch = Scratch::new();
+        for _ in 0..4 {
+            let inp = randstr(8..64);
+            let init = from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut);
+            assert_eq!(init.init(&scratch).await, inp);
+            assert!(!scratch.is_occupied());
+        }
+    }
+
+    #[test]
+    fn send_scratch() {
+        let scratch = Scratch::new();
+        std::thread::spawn(move || drop(scratch)).join().unwrap();
+    }
+
+    #[test]
+    fn reject_occupied_scratch() {
+        let scratch = Scratch::new();
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let out = init.init(&scratch);
+        assert!(scratch.is_occupied());
+
+        let init = from_closure(|slot| slot.write(123) as &mut OpqAny);
+        let (init, _) = init.try_init(&scratch).unwrap_err();
+        drop(out);
+        assert_eq!(DropCounter::count(), 1);
+        assert!(init.try_init(&scratch).is_ok());
+    }
+
+    #[test]
+    fn occupied_during_construction() {
+        let scratch = Scratch::new();
+        let init = from_closure(|slot| {
+            let inner = from_closure(|slot| slot.write(123));
+            assert!(inner.try_init(&scratch).is_err());
+            slot.write(456)
+        });
+        assert_eq!(*init.init(&scratch), 456);
+    }
+
+    #[test]
+    fn occupied_during_destruction() {
+        struct Reuse<'a>(&'a Scratch);
+        impl Drop for Reuse<'_> {
+            fn drop(&mut self) {
+                let init = from_closure(|slot| slot.write(123));
+                assert!(init.try_init(self.0).is_err());
+            }
+        }
+
+        let scratch = Scratch::new();
+        let init = from_closure(|slot| slot.write(Reuse(&scratch)));
+        drop(init.init(&scratch));
+        assert!(!scratch.is_occupied());
+    }
+
+    #[test]
+    fn release_scratch_on_panic() {
+        let scratch = Scratch::new();
+        let init = from_closure::<usize, usize, _>(|_| panic!("just panic"));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| init.init(&scratch)));
+        assert!(res.is_err());
+        assert!(!scratch.is_occupied());
+    }
+
+    #[test]
+    fn pin_init_scratch() {
+        let scratch = Scratch::new();
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let _: Pin<ScratchBuffered<PhantomPinned>> = init.pin_init(&scratch);
+    }
+
+    #[test]
+    fn reuse_cell_buffer() {
+        let buf = CellBuffer::<16>::new();
+        for _ in 0..4 {
+            let inp = randarr::<16>();
+            let init = from_closure(|slot| slot.write(inp) as &mut OpqAny);
+            let out = init.init(&buf);
+            assert!(buf.is_occupied());
+            assert_eq!(out.downcast_ref::<[u8; 16]>(), Some(&inp));
+            drop(out);
+            assert!(!buf.is_occupied());
+        }
+    }
+
+    #[test]
+    fn reject_cell_buffer() {
+        let buf = CellBuffer::<16>::new();
+        let init = from_closure(|slot| slot.write(randarr::<17>()) as &mut OpqAny);
+        assert!(init.try_init(&buf).is_err());
+        assert!(!buf.is_occupied());
+
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let out = init.init(&buf);
+        let init = from_closure(|slot| slot.write(123) as &mut OpqAny);
+        assert!(init.try_init(&buf).is_err());
+        drop(out);
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[test]
+    fn reclaim_leaked_cell_buffer() {
+        let mut buf = CellBuffer::<16>::new();
+        let init = from_closure(|slot| slot.write(123) as &mut OpqAny);
+        std::mem::forget(init.init(&buf));
+        assert!(buf.is_occupied());
+
+        buf.reclaim();
+        assert!(!buf.is_occupied());
+        let init = from_closure(|slot| slot.write(456) as &mut OpqAny);
+        assert_eq!(init.init(&buf).downcast_ref::<i32>(), Some(&456));
+    }
+
+    #[cfg_attr(miri, ignore)] // leaks memory intentionally
+    #[test]
+    fn reclaim_leaked_scratch() {
+        let mut scratch = Scratch::with_capacity(16);
+        let init = from_closure(|slot| slot.write(randstr(8..64)) as &mut OpqAny);
+        std::mem::forget(init.init(&scratch));
+        assert!(scratch.is_occupied());
+
+        scratch.reclaim();
+        assert!(!scratch.is_occupied());
+        assert_eq!(scratch.capacity(), 0);
+    }
+}
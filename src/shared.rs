@@ -0,0 +1,246 @@
+use alloc::rc::Rc;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use core::alloc::Layout;
+use core::convert::Infallible;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, Slot};
+use crate::container::{CapacityHint, Emplace, PinEmplace};
+use crate::container_core::construct_in;
+
+/// A unit type to perform constructions in [`Rc`].
+///
+/// Objects are constructed directly in a fresh `Rc` allocation, rather than
+/// constructed in a [`Box`] and then copied to an `Rc`. Hence, it can also be
+/// used for pinned constructions, which yield `Pin<Rc<T>>`. Objects aligned
+/// to more than 64 KiB are not supported, for which [`handle_alloc_error`] is
+/// called.
+///
+/// [`Box`]: alloc::boxed::Box
+/// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct Rced;
+
+/// A unit type to perform constructions in [`Arc`].
+///
+/// Objects are constructed directly in a fresh `Arc` allocation, rather than
+/// constructed in a [`Box`] and then copied to an `Arc`. Hence, it can also be
+/// used for pinned constructions, which yield `Pin<Arc<T>>`. Objects aligned
+/// to more than 64 KiB are not supported, for which [`handle_alloc_error`] is
+/// called.
+///
+/// [`Box`]: alloc::boxed::Box
+/// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+#[cfg(target_has_atomic = "ptr")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct Arced;
+
+macro_rules! impl_shared {
+    ($container:ident, $ptr:ident, $alloc:ident) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        unsafe impl<T: ?Sized> Emplace<T> for $container {
+            type Ptr = $ptr<T>;
+            type Err = Infallible;
+
+            fn emplace<C>(self, constructor: C) -> Result<Self::Ptr, Self::Err>
+            where
+                C: Construct<Object = T>,
+            {
+                let layout = constructor.layout();
+                let block =
+                    $alloc(layout).unwrap_or_else(|| alloc::alloc::handle_alloc_error(layout));
+                unsafe {
+                    let release_on_panic =
+                        crate::utils::defer(|| (block.release)(block.ptr, block.len));
+                    let init = construct_in(Slot::new_unchecked(block.ptr), constructor, layout);
+                    core::mem::forget(release_on_panic);
+                    // `from_raw` requires the memory block to be allocated for a
+                    // value of the same size and alignment, which the units
+                    // satisfy. Therefore, the object is released as if it was
+                    // allocated for itself.
+                    Ok($ptr::from_raw(init.as_ptr()))
+                }
+            }
+        }
+        // SAFETY: The object is constructed in place and never moved, and the
+        // allocation is not released until all references are dropped.
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        unsafe impl<T: ?Sized> PinEmplace<T> for $container {}
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl CapacityHint for $container {
+            fn can_fit(&self, layout: Layout) -> bool {
+                layout.align() <= MAX_ALIGN
+            }
+        }
+
+        /// Allocates a shared slice of units whose size and alignment are both
+        /// `layout.align()`, which is large enough to hold `layout`.
+        ///
+        /// It is generic over neither constructors nor objects, so that only
+        /// one instance per alignment is ever compiled. Returns `None` if the
+        /// alignment is greater than [`MAX_ALIGN`].
+        fn $alloc(layout: Layout) -> Option<SharedBlock> {
+            unsafe fn release<U>(ptr: NonNull<u8>, len: usize) {
+                let units =
+                    core::ptr::slice_from_raw_parts(ptr.cast::<MaybeUninit<U>>().as_ptr(), len);
+                drop($ptr::from_raw(units));
+            }
+
+            with_unit_of!(layout.align(), |U| {
+                debug_assert_eq!(layout.align(), core::mem::size_of::<U>());
+                let len = layout.size() / core::mem::size_of::<U>();
+                // Iterators of known length are collected without reallocation.
+                let units = (0..len)
+                    .map(|_| MaybeUninit::<U>::uninit())
+                    .collect::<$ptr<[_]>>();
+                let ptr = $ptr::into_raw(units).cast_mut().cast::<u8>();
+                return Some(SharedBlock {
+                    ptr: unsafe { NonNull::new_unchecked(ptr) },
+                    len,
+                    release: release::<U>,
+                });
+            });
+            None
+        }
+    };
+}
+
+/// The largest alignment supported by [`Rced`] and [`Arced`].
+///
+/// Units of the same size are moved through the stack when allocated, hence
+/// the limit, which is the size of the largest pages on common platforms.
+const MAX_ALIGN: usize = 65536;
+
+/// A type-erased shared slice of units.
+struct SharedBlock {
+    ptr: NonNull<u8>,
+    len: usize,
+    /// Releases the slice, in case the construction panics.
+    release: unsafe fn(NonNull<u8>, usize),
+}
+
+/// Executes `$body` with `$U` defined as a unit type of the given alignment,
+/// if the alignment is supported.
+macro_rules! with_unit_of {
+    ($align:expr, |$U:ident| $body:expr) => {
+        with_unit_of!(@ $align, |$U| $body;
+            1 => Align1, 2 => Align2, 4 => Align4, 8 => Align8, 16 => Align16,
+            32 => Align32, 64 => Align64, 128 => Align128, 256 => Align256,
+            512 => Align512, 1024 => Align1024, 2048 => Align2048, 4096 => Align4096,
+            8192 => Align8192, 16384 => Align16384, 32768 => Align32768,
+            65536 => Align65536,
+        )
+    };
+    (@ $align:expr, |$U:ident| $body:expr; $($n:literal => $unit:ident,)*) => {{
+        $(#[repr(C, align($n))] struct $unit(#[allow(dead_code)] [u8; $n]);)*
+        match $align {
+            $($n => {
+                type $U = $unit;
+                $body
+            })*
+            _ => {},
+        }
+    }};
+}
+
+impl_shared!(Rced, Rc, rc_alloc);
+#[cfg(target_has_atomic = "ptr")]
+impl_shared!(Arced, Arc, arc_alloc);
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::marker::PhantomPinned;
+    use std::pin::Pin;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::utils::{randarr, randstr, DropCounter, OpqAny};
+    use crate::{from_closure, Dynify, PinDynify};
+
+    #[repr(align(8192))]
+    struct OverAligned(u8);
+
+    #[rstest]
+    #[case(randstr(8..64))]
+    #[case(randarr::<0>())]
+    #[case(randarr::<33>())]
+    #[case(fastrand::u128(..))]
+    fn init_in_rc<T: Any + Clone + PartialEq + std::fmt::Debug>(#[case] inp: T) {
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        let out: Rc<dyn Any> = init.init(Rced);
+        let cloned = out.clone();
+        assert_eq!(cloned.downcast_ref::<T>(), Some(&inp));
+
+        let init = from_closure(|slot| slot.write(inp.clone()) as &mut OpqAny);
+        let out: Arc<dyn Any> = init.init(Arced);
+        let cloned = out.clone();
+        assert_eq!(cloned.downcast_ref::<T>(), Some(&inp));
+    }
+
+    #[test]
+    fn drop_shared() {
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let out = init.init(Rced);
+        drop((out.clone(), out));
+        assert_eq!(DropCounter::count(), 1);
+
+        let init = from_closure(|slot| slot.write(DropCounter) as &mut OpqAny);
+        let out = init.init(Arced);
+        drop((out.clone(), out));
+        assert_eq!(DropCounter::count(), 2);
+    }
+
+    #[test]
+    fn init_in_place() {
+        let mut addr = None;
+        let init = from_closure(|slot| {
+            addr = Some(slot.as_ptr());
+            slot.write(randarr::<64>()) as &mut OpqAny
+        });
+        let out = init.rc();
+        assert_eq!(addr, NonNull::new(Rc::as_ptr(&out) as *mut u8));
+
+        let mut addr = None;
+        let init = from_closure(|slot| {
+            addr = Some(slot.as_ptr());
+            slot.write(randarr::<64>()) as &mut OpqAny
+        });
+        let out = init.arc();
+        assert_eq!(addr, NonNull::new(Arc::as_ptr(&out) as *mut u8));
+    }
+
+    #[test]
+    fn init_over_aligned() {
+        let init = from_closure(|slot| slot.write(OverAligned(7)) as &mut OpqAny);
+        let out = init.init(Rced);
+        assert_eq!(out.downcast_ref::<OverAligned>().map(|v| v.0), Some(7));
+    }
+
+    #[test]
+    fn reject_huge_alignments() {
+        assert!(Rced.can_fit(Layout::new::<OverAligned>()));
+        assert!(!Arced.can_fit(Layout::from_size_align(1, 1 << 17).unwrap()));
+    }
+
+    #[test]
+    fn pin_init_shared() {
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let out: Pin<Rc<PhantomPinned>> = init.pin_rc();
+        drop(out.clone());
+
+        let init = from_closure(|slot| slot.write(OverAligned(7)));
+        let out = init.pin_init(Rced);
+        assert_eq!(out.0, 7);
+
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let out: Pin<Arc<PhantomPinned>> = init.pin_arc();
+        drop(out.clone());
+    }
+}
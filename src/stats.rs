@@ -0,0 +1,204 @@
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::fmt;
+
+use crate::constructor::{Construct, PinConstruct};
+use crate::container::{CapacityHint, Emplace, PinEmplace};
+
+/// Statistics recorded by [`Tracked`] containers.
+///
+/// It counts successful and failed constructions, along with the sizes of the
+/// constructed objects. This helps to tune the size of stack buffers, for
+/// instance, by checking how often [`Dynify::init2`] falls back to the second
+/// container.
+///
+/// # Examples
+///
+/// ```rust
+/// # use dynify::{from_closure, ContainerStats, Dynify, Tracked};
+/// # use std::mem::MaybeUninit;
+/// let stats = ContainerStats::new();
+/// let mut stack = MaybeUninit::<[u8; 16]>::uninit();
+/// let mut heap = Vec::<MaybeUninit<u8>>::new();
+///
+/// let init = from_closure(|slot| slot.write([0u8; 32]));
+/// let _ = init.init2(Tracked::new(&mut stack, &stats), &mut heap);
+/// assert_eq!(stats.failed(), 1);
+/// assert_eq!(stats.max_size(), 32);
+/// ```
+///
+/// [`Dynify::init2`]: crate::Dynify::init2
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+#[derive(Default)]
+pub struct ContainerStats {
+    emplaced: Cell<usize>,
+    failed: Cell<usize>,
+    bytes: Cell<usize>,
+    max_size: Cell<usize>,
+}
+impl ContainerStats {
+    /// Creates a new instance with all counters set to zero.
+    pub const fn new() -> Self {
+        Self {
+            emplaced: Cell::new(0),
+            failed: Cell::new(0),
+            bytes: Cell::new(0),
+            max_size: Cell::new(0),
+        }
+    }
+
+    /// Returns the number of successful constructions.
+    pub fn emplaced(&self) -> usize {
+        self.emplaced.get()
+    }
+
+    /// Returns the number of failed constructions.
+    pub fn failed(&self) -> usize {
+        self.failed.get()
+    }
+
+    /// Returns the total size of constructed objects in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes.get()
+    }
+
+    /// Returns the size of the largest object ever requested, including those
+    /// failed to be constructed.
+    pub fn max_size(&self) -> usize {
+        self.max_size.get()
+    }
+
+    /// Sets all counters to zero.
+    pub fn reset(&self) {
+        self.emplaced.set(0);
+        self.failed.set(0);
+        self.bytes.set(0);
+        self.max_size.set(0);
+    }
+
+    fn record<P, E>(&self, layout: Layout, res: &Result<P, E>) {
+        let size = layout.size();
+        self.max_size.set(usize::max(self.max_size.get(), size));
+        if res.is_ok() {
+            self.emplaced.set(self.emplaced.get() + 1);
+            self.bytes.set(self.bytes.get().saturating_add(size));
+        } else {
+            self.failed.set(self.failed.get() + 1);
+        }
+    }
+}
+impl fmt::Debug for ContainerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContainerStats")
+            .field("emplaced", &self.emplaced())
+            .field("failed", &self.failed())
+            .field("bytes", &self.bytes())
+            .field("max_size", &self.max_size())
+            .finish()
+    }
+}
+
+/// A container that records its constructions in [`ContainerStats`].
+///
+/// It wraps another container and forwards all constructions to it. See
+/// [`ContainerStats`] for more information.
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+#[derive(Debug)]
+pub struct Tracked<'s, C> {
+    container: C,
+    stats: &'s ContainerStats,
+}
+impl<'s, C> Tracked<'s, C> {
+    /// Wraps `container`, recording its constructions in `stats`.
+    pub const fn new(container: C, stats: &'s ContainerStats) -> Self {
+        Self { container, stats }
+    }
+
+    /// Consumes this instance, returning the inner container.
+    pub fn into_inner(self) -> C {
+        self.container
+    }
+}
+
+unsafe impl<T, C> Emplace<T> for Tracked<'_, C>
+where
+    T: ?Sized,
+    C: Emplace<T>,
+{
+    type Ptr = C::Ptr;
+    type Err = C::Err;
+
+    fn emplace<D>(self, constructor: D) -> Result<Self::Ptr, Self::Err>
+    where
+        D: Construct<Object = T>,
+    {
+        let layout = constructor.layout();
+        let res = self.container.emplace(constructor);
+        self.stats.record(layout, &res);
+        res
+    }
+    fn available(err: &Self::Err) -> Option<usize> {
+        C::available(err)
+    }
+}
+unsafe impl<T, C> PinEmplace<T> for Tracked<'_, C>
+where
+    T: ?Sized,
+    C: PinEmplace<T>,
+{
+    fn pin_emplace<D>(self, constructor: D) -> Result<core::pin::Pin<Self::Ptr>, Self::Err>
+    where
+        D: PinConstruct<Object = T>,
+    {
+        let layout = constructor.layout();
+        let res = self.container.pin_emplace(constructor);
+        self.stats.record(layout, &res);
+        res
+    }
+}
+impl<C: CapacityHint> CapacityHint for Tracked<'_, C> {
+    fn can_fit(&self, layout: Layout) -> bool {
+        self.container.can_fit(layout)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::marker::PhantomPinned;
+
+    use super::*;
+    use crate::utils::{newstk, randarr, OpqAny};
+    use crate::{from_closure, Buffered, Dynify, PinBuffer, PinDynify};
+
+    #[test]
+    fn record_constructions() {
+        let stats = ContainerStats::new();
+        let mut stk = newstk::<16>();
+        for _ in 0..3 {
+            let init = from_closure(|slot| slot.write(randarr::<8>()) as &mut OpqAny);
+            let _: Buffered<dyn Any> = init.init(Tracked::new(&mut stk, &stats));
+        }
+        let init = from_closure(|slot| slot.write(randarr::<24>()) as &mut OpqAny);
+        assert!(init.try_init(Tracked::new(&mut stk, &stats)).is_err());
+
+        assert_eq!(stats.emplaced(), 3);
+        assert_eq!(stats.failed(), 1);
+        assert_eq!(stats.bytes(), 24);
+        assert_eq!(stats.max_size(), 24);
+
+        stats.reset();
+        assert_eq!(stats.emplaced(), 0);
+        assert_eq!(stats.max_size(), 0);
+    }
+
+    #[test]
+    fn record_pinned_constructions() {
+        let stats = ContainerStats::new();
+        let buf = std::pin::pin!(PinBuffer::<16>::new());
+        let init = from_closure(|slot| slot.write(PhantomPinned));
+        let _ = init.pin_init(Tracked::new(buf, &stats));
+        assert_eq!(stats.emplaced(), 1);
+    }
+}
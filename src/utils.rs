@@ -24,6 +24,30 @@ impl<F: FnOnce()> Drop for Defer<F> {
     }
 }
 
+/// Replaces the address of `ptr` with `addr`, keeping its metadata untouched.
+pub(crate) fn with_addr<T: ?Sized>(mut ptr: *mut T, addr: *mut u8) -> *mut T {
+    // SAFETY: The address is stored in the first field of both thin and fat
+    // pointers, and replacing it keeps the metadata untouched. The new address
+    // carries its own provenance.
+    unsafe { core::ptr::write(&mut ptr as *mut *mut T as *mut *mut u8, addr) };
+    ptr
+}
+
+/// Aborts the current process with the supplied message.
+///
+/// This is done by panicking while unwinding, which works in `no_std` as well.
+#[cold]
+pub(crate) fn abort(msg: &str) -> ! {
+    struct PanicOnDrop;
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            panic!("aborting the process");
+        }
+    }
+    let _guard = PanicOnDrop;
+    panic!("{}", msg);
+}
+
 #[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
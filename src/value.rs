@@ -0,0 +1,549 @@
+use core::alloc::Layout;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, PinConstruct, Slot};
+
+/// The constructor created by [`from_value`].
+#[derive(Clone)]
+#[must_use = "constructor must be initialized"]
+pub struct Value<T>(T);
+unsafe impl<T> PinConstruct for Value<T> {
+    type Object = T;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<T>();
+        ptr.as_ptr().write(self.0);
+        ptr
+    }
+}
+unsafe impl<T> Construct for Value<T> {}
+
+/// Creates a new constructor from an existing value.
+///
+/// The value is moved into the container once the returned instance gets
+/// [`construct`]ed. To construct it as a trait object on stable Rust, use
+/// [`from_closure`] with an unsizing coercion instead, e.g.,
+/// `from_closure(|slot| slot.write(value) as &mut Opaque<dyn Any>)`.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_value, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 32];
+/// let val = from_value(String::from("Hello!")).init(&mut stack);
+/// assert_eq!(*val, "Hello!");
+/// ```
+///
+/// [`construct`]: PinConstruct::construct
+/// [`from_closure`]: crate::from_closure
+#[inline(always)]
+pub fn from_value<T>(value: T) -> Value<T> {
+    Value(value)
+}
+
+/// The constructor created by [`from_default`].
+#[must_use = "constructor must be initialized"]
+pub struct Defaulted<T>(PhantomData<fn() -> T>);
+unsafe impl<T: Default> PinConstruct for Defaulted<T> {
+    type Object = T;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<T>();
+        ptr.as_ptr().write(T::default());
+        ptr
+    }
+}
+unsafe impl<T: Default> Construct for Defaulted<T> {}
+
+/// Creates a new constructor that calls [`Default::default`] at construction.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_default, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 64];
+/// let val = from_default::<[u32; 16]>().init(&mut stack);
+/// assert_eq!(*val, [0; 16]);
+/// ```
+#[inline(always)]
+pub fn from_default<T: Default>() -> Defaulted<T> {
+    Defaulted(PhantomData)
+}
+
+/// The constructor created by [`from_clone`].
+#[must_use = "constructor must be initialized"]
+pub struct Cloned<'a, T>(&'a T);
+unsafe impl<T: Clone> PinConstruct for Cloned<'_, T> {
+    type Object = T;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<T>();
+        ptr.as_ptr().write(T::clone(self.0));
+        ptr
+    }
+}
+unsafe impl<T: Clone> Construct for Cloned<'_, T> {}
+
+/// Creates a new constructor that clones `value` at construction.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_clone, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 32];
+/// let hello = String::from("Hello!");
+/// let val = from_clone(&hello).init(&mut stack);
+/// assert_eq!(*val, hello);
+/// ```
+#[inline(always)]
+pub fn from_clone<T: Clone>(value: &T) -> Cloned<'_, T> {
+    Cloned(value)
+}
+
+/// The constructor created by [`from_move`].
+#[must_use = "constructor must be initialized"]
+pub struct Moved<'a, T>(&'a mut ManuallyDrop<T>);
+unsafe impl<T> PinConstruct for Moved<'_, T> {
+    type Object = T;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<T>();
+        core::ptr::copy_nonoverlapping::<T>(&**self.0, ptr.as_ptr(), 1);
+        ptr
+    }
+}
+unsafe impl<T> Construct for Moved<'_, T> {}
+
+/// Creates a new constructor that moves the value out of `src` at
+/// construction.
+///
+/// The value is copied bitwise from `src` into the container, without being
+/// moved through the stack first. This is useful when a value is staged in
+/// some storage before it is moved into its final container. If the returned
+/// instance is dropped without being constructed, the value is left in `src`.
+///
+/// # Safety
+///
+/// Once the returned instance is constructed, the value in `src` is moved
+/// out. As with [`ManuallyDrop::take`], the caller must ensure that `src` is
+/// not used again unless it is reinitialized.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_move, Dynify};
+/// # use std::mem::{ManuallyDrop, MaybeUninit};
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 32];
+/// let mut staged = ManuallyDrop::new(String::from("Hello!"));
+/// let val = unsafe { from_move(&mut staged) }.init(&mut stack);
+/// assert_eq!(*val, "Hello!");
+/// ```
+#[inline(always)]
+pub unsafe fn from_move<T>(src: &mut ManuallyDrop<T>) -> Moved<'_, T> {
+    Moved(src)
+}
+
+/// The constructor created by [`from_iter`].
+#[must_use = "constructor must be initialized"]
+pub struct FromIter<I> {
+    iter: I,
+    len: usize,
+}
+unsafe impl<I: Iterator> PinConstruct for FromIter<I> {
+    type Object = [I::Item];
+    fn layout(&self) -> Layout {
+        Layout::array::<I::Item>(self.len).expect("slice is too large")
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<I::Item>();
+        let mut guard = SliceGuard { ptr, len: 0 };
+        for item in self.iter.take(self.len) {
+            ptr.add(guard.len).write(item);
+            guard.len += 1;
+        }
+        assert!(
+            guard.len == self.len,
+            "iterator yields fewer items than expected"
+        );
+        core::mem::forget(guard);
+        NonNull::slice_from_raw_parts(ptr, self.len)
+    }
+}
+unsafe impl<I: Iterator> Construct for FromIter<I> {}
+
+/// Creates a new constructor that collects `iter` into a slice.
+///
+/// The length of the slice is determined by [`ExactSizeIterator::len`] when
+/// this function is called. Extra items are ignored at construction.
+///
+/// # Panic
+///
+/// The returned constructor panics if the size of the slice overflows, or if
+/// `iter` yields fewer items than its reported length.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_iter, Buffered, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 32];
+/// let val: Buffered<[u32]> = from_iter(1..5).init(&mut stack);
+/// assert_eq!(&*val, [1, 2, 3, 4]);
+/// ```
+#[inline(always)]
+pub fn from_iter<I>(iter: I) -> FromIter<I::IntoIter>
+where
+    I: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+{
+    let iter = iter.into_iter();
+    let len = iter.len();
+    FromIter { iter, len }
+}
+
+/// The constructor created by [`from_fn_array`].
+#[must_use = "constructor must be initialized"]
+pub struct FromFnArray<T, const N: usize, F>(F, PhantomData<fn() -> T>);
+unsafe impl<T, const N: usize, F> PinConstruct for FromFnArray<T, N, F>
+where
+    F: FnMut(usize) -> T,
+{
+    type Object = [T; N];
+    fn layout(&self) -> Layout {
+        Layout::new::<[T; N]>()
+    }
+    unsafe fn construct(mut self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<T>();
+        let mut guard = SliceGuard { ptr, len: 0 };
+        for i in 0..N {
+            ptr.add(i).write((self.0)(i));
+            guard.len += 1;
+        }
+        core::mem::forget(guard);
+        ptr.cast()
+    }
+}
+unsafe impl<T, const N: usize, F> Construct for FromFnArray<T, N, F> where F: FnMut(usize) -> T {}
+
+/// Creates a new constructor that fills an array with the return values of
+/// `f`.
+///
+/// It works like [`core::array::from_fn`], except that the elements are
+/// written to the container one by one, without building the array elsewhere
+/// first. If `f` panics, the initialized elements are dropped.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_fn_array, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 32];
+/// let val = from_fn_array::<_, 4, _>(|i| i as u32 * 2).init(&mut stack);
+/// assert_eq!(*val, [0, 2, 4, 6]);
+/// ```
+#[inline(always)]
+pub fn from_fn_array<T, const N: usize, F>(f: F) -> FromFnArray<T, N, F>
+where
+    F: FnMut(usize) -> T,
+{
+    FromFnArray(f, PhantomData)
+}
+
+/// Drops the initialized elements of a slice on unwinding or early returns.
+struct SliceGuard<T> {
+    ptr: NonNull<T>,
+    len: usize,
+}
+impl<T> Drop for SliceGuard<T> {
+    fn drop(&mut self) {
+        unsafe { NonNull::slice_from_raw_parts(self.ptr, self.len).drop_in_place() }
+    }
+}
+
+/// The constructor created by [`from_str`] and [`from_bytes`].
+#[must_use = "constructor must be initialized"]
+pub struct Copied<'a, T: ?Sized>(&'a T);
+unsafe impl<T: Copy> PinConstruct for Copied<'_, [T]> {
+    type Object = [T];
+    fn layout(&self) -> Layout {
+        Layout::for_value(self.0)
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<T>();
+        let len = self.0.len();
+        core::ptr::copy_nonoverlapping(self.0.as_ptr(), ptr.as_ptr(), len);
+        NonNull::slice_from_raw_parts(ptr, len)
+    }
+}
+unsafe impl<T: Copy> Construct for Copied<'_, [T]> {}
+unsafe impl PinConstruct for Copied<'_, str> {
+    type Object = str;
+    fn layout(&self) -> Layout {
+        Layout::for_value(self.0)
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = Copied(self.0.as_bytes()).construct(slot);
+        NonNull::new_unchecked(ptr.as_ptr() as *mut str)
+    }
+}
+unsafe impl Construct for Copied<'_, str> {}
+
+/// Creates a new constructor that copies `s` into containers.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_str, Buffered, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+/// let val: Buffered<str> = from_str("Hello!").init(&mut stack);
+/// assert_eq!(&*val, "Hello!");
+/// ```
+#[inline(always)]
+pub fn from_str(s: &str) -> Copied<'_, str> {
+    Copied(s)
+}
+
+/// Creates a new constructor that copies `bytes` into containers.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_bytes, Buffered, Dynify};
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 16];
+/// let val: Buffered<[u8]> = from_bytes(b"Hello!").init(&mut stack);
+/// assert_eq!(&*val, b"Hello!");
+/// ```
+#[inline(always)]
+pub fn from_bytes(bytes: &[u8]) -> Copied<'_, [u8]> {
+    Copied(bytes)
+}
+
+/// The constructor created by [`from_value_as`].
+#[cfg(dynify_unsize)]
+#[must_use = "constructor must be initialized"]
+pub struct ValueAs<T, U: ?Sized>(T, PhantomData<fn() -> *const U>);
+#[cfg(dynify_unsize)]
+unsafe impl<T, U> PinConstruct for ValueAs<T, U>
+where
+    T: core::marker::Unsize<U>,
+    U: ?Sized,
+{
+    type Object = U;
+    fn layout(&self) -> Layout {
+        Layout::new::<T>()
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let ptr = slot.as_ptr().cast::<T>();
+        ptr.as_ptr().write(self.0);
+        ptr
+    }
+}
+#[cfg(dynify_unsize)]
+unsafe impl<T, U> Construct for ValueAs<T, U>
+where
+    T: core::marker::Unsize<U>,
+    U: ?Sized,
+{
+}
+
+/// Creates a new constructor from an existing value, which is coerced to `U`.
+///
+/// It works the same as [`from_value`], but the object is constructed as an
+/// unsized type, such as a trait object. It is only available on nightly Rust
+/// with `--cfg dynify_unsize`.
+#[cfg(dynify_unsize)]
+#[inline(always)]
+pub fn from_value_as<U: ?Sized, T: core::marker::Unsize<U>>(value: T) -> ValueAs<T, U> {
+    ValueAs(value, PhantomData)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::utils::{newstk, randarr, randstr, DropCounter};
+    use crate::{Dynify, PinDynify};
+
+    #[test]
+    fn init_from_value() {
+        let mut stk = newstk::<32>();
+        let inp = randstr(8..16);
+        let out = from_value(inp.clone()).init(&mut stk);
+        assert_eq!(*out, inp);
+        drop(out);
+
+        let out = from_value(DropCounter).boxed();
+        drop(out);
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[pollster::test]
+    async fn pin_init_from_value() {
+        let inp = randstr(8..16);
+        let out = from_value(async { inp.clone() }).pin_boxed().await;
+        assert_eq!(out, inp);
+    }
+
+    #[test]
+    fn init_from_default() {
+        let mut stk = newstk::<32>();
+        let out = from_default::<String>().init(&mut stk);
+        assert_eq!(*out, "");
+        drop(out);
+
+        let out = from_default::<[u64; 4]>().init(&mut stk);
+        assert_eq!(*out, [0; 4]);
+    }
+
+    #[test]
+    fn init_from_clone() {
+        let mut stk = newstk::<32>();
+        let inp = randstr(8..16);
+        let out = from_clone(&inp).init(&mut stk);
+        assert_eq!(*out, inp);
+        drop(out);
+
+        let out = from_clone(&inp).boxed();
+        assert_eq!(*out, inp);
+    }
+
+    #[test]
+    fn init_from_move() {
+        let mut stk = newstk::<32>();
+        let inp = randstr(8..16);
+        let mut src = ManuallyDrop::new(inp.clone());
+        let out = unsafe { from_move(&mut src) }.init(&mut stk);
+        assert_eq!(*out, inp);
+        drop(out);
+
+        let mut src = ManuallyDrop::new(DropCounter);
+        drop(unsafe { from_move(&mut src) });
+        assert_eq!(DropCounter::count(), 0);
+        let out = unsafe { from_move(&mut src) }.boxed();
+        drop(out);
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[test]
+    fn init_from_iter() {
+        let mut stk = newstk::<128>();
+        let inp = (0..fastrand::usize(1..4))
+            .map(|_| randstr(8..16))
+            .collect::<Vec<_>>();
+        let out = from_iter(inp.iter().cloned()).init(&mut stk);
+        assert_eq!(&*out, &*inp);
+        drop(out);
+
+        let out = from_iter(std::iter::empty::<String>()).boxed();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn ignore_extra_items() {
+        struct Liar<I>(I, usize);
+        impl<I: Iterator> Iterator for Liar<I> {
+            type Item = I::Item;
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next()
+            }
+        }
+        impl<I: Iterator> ExactSizeIterator for Liar<I> {
+            fn len(&self) -> usize {
+                self.1
+            }
+        }
+
+        let out = from_iter(Liar(0..8, 3)).boxed();
+        assert_eq!(&*out, [0, 1, 2]);
+    }
+
+    #[test]
+    fn panic_on_short_iterator() {
+        struct Liar(usize);
+        impl Iterator for Liar {
+            type Item = DropCounter;
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0 = self.0.checked_sub(1)?;
+                Some(DropCounter)
+            }
+        }
+        impl ExactSizeIterator for Liar {
+            fn len(&self) -> usize {
+                4
+            }
+        }
+
+        let res = std::panic::catch_unwind(|| drop(from_iter(Liar(2)).boxed()));
+        let err = res.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<&str>(),
+            Some(&"iterator yields fewer items than expected"),
+        );
+        assert_eq!(DropCounter::count(), 2);
+    }
+
+    #[test]
+    fn init_from_fn_array() {
+        let mut stk = newstk::<128>();
+        let inp = [(); 3].map(|_| randstr(8..16));
+        let out = from_fn_array::<_, 3, _>(|i| inp[i].clone()).init(&mut stk);
+        assert_eq!(*out, inp);
+        drop(out);
+
+        let out = from_fn_array::<String, 0, _>(|_| unreachable!()).boxed();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn drop_array_items_on_panic() {
+        let init = from_fn_array::<_, 4, _>(|i| {
+            assert!(i < 3, "generator panicked");
+            DropCounter
+        });
+        let res = std::panic::catch_unwind(|| drop(init.boxed()));
+        assert!(res.is_err());
+        assert_eq!(DropCounter::count(), 3);
+    }
+
+    #[test]
+    fn init_from_str() {
+        let mut stk = newstk::<16>();
+        let inp = randstr(0..16);
+        let out = from_str(&inp).init(&mut stk);
+        assert_eq!(&*out, inp);
+        drop(out);
+
+        let inp = randarr::<16>();
+        let out = from_bytes(&inp).init(&mut stk);
+        assert_eq!(*out, inp);
+    }
+
+    #[cfg(dynify_unsize)]
+    #[test]
+    fn init_from_value_as() {
+        let inp = randstr(8..16);
+        let out = from_value_as::<dyn std::any::Any, _>(inp.clone()).boxed();
+        assert_eq!(out.downcast_ref::<String>(), Some(&inp));
+    }
+
+    #[test]
+    fn drop_unused_value() {
+        drop(from_value(DropCounter));
+        assert_eq!(DropCounter::count(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+use core::alloc::Layout;
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+use crate::constructor::{Construct, PinConstruct, Slot};
+
+/// A pair of objects constructed by [`zip`].
+///
+/// It is laid out as a `#[repr(C)]` struct, so that `tail` can be unsized,
+/// e.g., `Pair<Header, dyn Future<Output = T>>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Pair<A, B: ?Sized> {
+    /// The first object.
+    pub head: A,
+    /// The second object, which may be unsized.
+    pub tail: B,
+}
+impl<A, B: ?Sized> Pair<A, B> {
+    /// Returns a pinned mutable reference to the first object.
+    pub fn project_head(self: Pin<&mut Self>) -> Pin<&mut A> {
+        unsafe { self.map_unchecked_mut(|this| &mut this.head) }
+    }
+
+    /// Returns a pinned mutable reference to the second object.
+    pub fn project_tail(self: Pin<&mut Self>) -> Pin<&mut B> {
+        unsafe { self.map_unchecked_mut(|this| &mut this.tail) }
+    }
+}
+
+/// The constructor created by [`zip`].
+#[derive(Clone)]
+#[must_use = "constructor must be initialized"]
+pub struct Zip<C1, C2>(C1, C2);
+impl<C1, C2> Zip<C1, C2>
+where
+    C1: PinConstruct,
+    C1::Object: Sized,
+    C2: PinConstruct,
+{
+    /// Returns the layout of the pair along with the offset of its tail.
+    fn layout_and_offset(&self) -> (Layout, usize) {
+        let (layout, offset) = Layout::new::<C1::Object>()
+            .extend(self.1.layout())
+            .expect("pair is too large");
+        (layout.pad_to_align(), offset)
+    }
+}
+unsafe impl<C1, C2> PinConstruct for Zip<C1, C2>
+where
+    C1: PinConstruct,
+    C1::Object: Sized,
+    C2: PinConstruct,
+{
+    type Object = Pair<C1::Object, C2::Object>;
+    fn layout(&self) -> Layout {
+        self.layout_and_offset().0
+    }
+    unsafe fn construct(self, slot: Slot) -> NonNull<Self::Object> {
+        let (_, offset) = self.layout_and_offset();
+        let ptr = slot.into_raw();
+        let head = self.0.construct(Slot::new_unchecked(ptr));
+        // Drop the head if the tail panics.
+        let guard = crate::utils::defer(|| head.drop_in_place());
+        let tail = self.1.construct(Slot::new_unchecked(ptr.add(offset)));
+        core::mem::forget(guard);
+        // The pair shares the metadata of its tail.
+        let pair = tail.as_ptr() as *mut Self::Object;
+        NonNull::new_unchecked(crate::utils::with_addr(pair, ptr.as_ptr()))
+    }
+}
+unsafe impl<C1, C2> Construct for Zip<C1, C2>
+where
+    C1: Construct,
+    C1::Object: Sized,
+    C2: Construct,
+{
+}
+
+/// Combines two constructors into one that constructs a [`Pair`] of both
+/// objects in a single memory block.
+///
+/// The object of `c1` must be sized, while that of `c2` can be unsized. This
+/// is useful, for instance, to pack a dynamic future together with a small
+/// header.
+///
+/// # Example
+///
+/// ```rust
+/// # use dynify::{from_closure, zip, Buffered, Dynify, Pair};
+/// # use std::fmt::Display;
+/// # use std::mem::MaybeUninit;
+/// let mut stack = [MaybeUninit::<u8>::uninit(); 32];
+/// let head = from_closure(|slot| slot.write(7u8));
+/// let tail = from_closure(|slot| slot.write(String::from("Hi!")) as &mut _);
+/// let val: Buffered<Pair<u8, dyn Display>> = zip(head, tail).init(&mut stack);
+/// assert_eq!(val.head, 7);
+/// assert_eq!(val.tail.to_string(), "Hi!");
+/// ```
+#[inline(always)]
+pub fn zip<C1, C2>(c1: C1, c2: C2) -> Zip<C1, C2>
+where
+    C1: PinConstruct,
+    C1::Object: Sized,
+    C2: PinConstruct,
+{
+    Zip(c1, c2)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::any::Any;
+    use std::future::Future;
+
+    use super::*;
+    use crate::utils::{newstk, randarr, randstr, DropCounter, OpqAny, OpqStrFut};
+    use crate::{from_closure, from_value, Dynify, PinDynify};
+
+    #[test]
+    fn init_pair() {
+        let mut stk = newstk::<64>();
+        let head = randarr::<3>();
+        let tail = randstr(8..16);
+        let init = zip(
+            from_value(head),
+            from_closure(|slot| slot.write(tail.clone()) as &mut OpqAny),
+        );
+        let layout = init.layout();
+        let out = init.init(&mut stk);
+        assert_eq!(Layout::for_value(&*out), layout);
+        assert_eq!(out.head, head);
+        assert_eq!(out.tail.downcast_ref::<String>(), Some(&tail));
+
+        let init = zip(from_value(1u8), from_value(2u64));
+        let out = init.boxed();
+        assert_eq!(*out, Pair { head: 1, tail: 2 });
+        assert_eq!(size_of_val(&*out), size_of::<Pair<u8, u64>>());
+    }
+
+    #[pollster::test]
+    async fn pin_init_pair() {
+        let inp = randstr(8..16);
+        let init = zip(
+            from_value(DropCounter),
+            from_closure(|slot| slot.write(async { inp.clone() }) as &mut OpqStrFut),
+        );
+        let mut out: Pin<Box<Pair<_, dyn Future<Output = String>>>> = init.pin_boxed();
+        assert_eq!(out.as_mut().project_tail().await, inp);
+        drop(out);
+        assert_eq!(DropCounter::count(), 1);
+    }
+
+    #[test]
+    fn drop_head_on_panic() {
+        let init = zip(
+            from_value(DropCounter),
+            from_closure::<String, dyn Any, _>(|_| panic!("tail panicked")),
+        );
+        let res = std::panic::catch_unwind(|| drop(init.boxed()));
+        assert!(res.is_err());
+        assert_eq!(DropCounter::count(), 1);
+    }
+}